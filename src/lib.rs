@@ -5,13 +5,330 @@ use syn::{
     parse_quote,
     spanned::Spanned as _,
     ConstParam, GenericArgument, GenericParam, Generics, Ident, ItemStruct, LifetimeParam, Path,
-    PathArguments, PathSegment, Type, TypeParam, TypePath,
+    PathArguments, PathSegment, Type, TypeParam, TypePath, TypeSlice,
 };
 
 struct Methods {
     methods: Vec<(syn::Ident, Method)>,
 }
 
+/// Options that add to the generated code instead of selecting methods, so they can be combined
+/// with the default implementation.
+#[derive(Default)]
+struct Options {
+    deref: Option<(proc_macro2::Span, DerefTarget)>,
+    deref_mut: Option<proc_macro2::Span>,
+    as_ref: Option<proc_macro2::Span>,
+    as_mut: Option<proc_macro2::Span>,
+    borrow: Option<proc_macro2::Span>,
+    default: Option<proc_macro2::Span>,
+    debug: Option<DebugOptions>,
+    display: Option<DisplayOptions>,
+    from_str: Option<FromStrOptions>,
+    partial_eq: Option<proc_macro2::Span>,
+    into_slice: Option<proc_macro2::Span>,
+    concat: Option<proc_macro2::Span>,
+    hash_unordered: Option<proc_macro2::Span>,
+    derives: Vec<Ident>,
+    non_empty: Option<proc_macro2::Span>,
+    min_len: Option<(proc_macro2::Span, usize)>,
+    max_len: Option<(proc_macro2::Span, usize)>,
+    /// The span of `sorted`, and the key of `sorted_by_key`.
+    sorted: Option<(proc_macro2::Span, Option<TokenStream>)>,
+    unique: Option<proc_macro2::Span>,
+}
+
+struct DebugOptions {
+    span: proc_macro2::Span,
+    style: DebugStyle,
+    max_items: Option<usize>,
+    sorted: Option<proc_macro2::Span>,
+}
+
+struct DisplayOptions {
+    span: proc_macro2::Span,
+    sep: syn::LitStr,
+    prefix: syn::LitStr,
+    suffix: syn::LitStr,
+    kv_sep: syn::LitStr,
+}
+
+struct FromStrOptions {
+    span: proc_macro2::Span,
+    sep: syn::LitStr,
+    kv_sep: syn::LitStr,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DebugStyle {
+    /// `[1, 2, 3]`
+    Transparent,
+    /// `Numbers[1, 2, 3]`
+    Named,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DerefTarget {
+    Inner,
+    Slice,
+}
+
+impl Options {
+    /// Returns `Ok(false)` if `meta` is not an option.
+    fn parse_meta(&mut self, meta: &syn::Meta) -> syn::Result<bool> {
+        let Some(ident) = meta.path().get_ident() else {
+            return Ok(false);
+        };
+        match ident.to_string().as_str() {
+            "deref" | "deref_mut" => {
+                let target = match meta {
+                    syn::Meta::Path(_) => None,
+                    syn::Meta::NameValue(name_value) => {
+                        Some(DerefTarget::from_expr(&name_value.value)?)
+                    }
+                    syn::Meta::List(list) => {
+                        return Err(syn::Error::new(
+                            list.delimiter.span().open(),
+                            "expected `deref` or `deref = inner | slice`",
+                        ))
+                    }
+                };
+                if ident == "deref_mut" {
+                    self.deref_mut = Some(ident.span());
+                }
+                match (self.deref, target) {
+                    (Some((_, existing)), Some(target)) if existing != target => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "conflicting targets for `deref` and `deref_mut`",
+                        ));
+                    }
+                    (Some(_), None) => {}
+                    (_, target) => {
+                        self.deref = Some((ident.span(), target.unwrap_or(DerefTarget::Inner)));
+                    }
+                }
+            }
+            "as_ref" => self.as_ref = Some(meta.require_path_only()?.span()),
+            "as_mut" => self.as_mut = Some(meta.require_path_only()?.span()),
+            "borrow" => self.borrow = Some(meta.require_path_only()?.span()),
+            "default" => self.default = Some(meta.require_path_only()?.span()),
+            "partial_eq" => self.partial_eq = Some(meta.require_path_only()?.span()),
+            "into_slice" => self.into_slice = Some(meta.require_path_only()?.span()),
+            "concat" => self.concat = Some(meta.require_path_only()?.span()),
+            "non_empty" => self.non_empty = Some(meta.require_path_only()?.span()),
+            "unique" => self.unique = Some(meta.require_path_only()?.span()),
+            "sorted" => {
+                let key = self.sorted.take().and_then(|(_, key)| key);
+                self.sorted = Some((meta.require_path_only()?.span(), key));
+            }
+            "sorted_by_key" => {
+                let key = &meta.require_name_value()?.value;
+                // Resolve local variables in the key at the mixed site, so that the locals of the
+                // generated code don't shadow a key function of the same name (e.g. `len`).
+                let key = respan_mixed_site(key.to_token_stream());
+                self.sorted = Some((ident.span(), Some(key)));
+            }
+            "min_len" | "max_len" => {
+                let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(len),
+                    ..
+                }) = &meta.require_name_value()?.value
+                else {
+                    return Err(syn::Error::new(
+                        meta.span(),
+                        format!("expected `{ident} = N`"),
+                    ));
+                };
+                let len = Some((ident.span(), len.base10_parse()?));
+                if ident == "min_len" {
+                    self.min_len = len;
+                } else {
+                    self.max_len = len;
+                }
+            }
+            "hash" => {
+                meta.require_list()?.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("unordered") {
+                        self.hash_unordered = Some(nested.path.span());
+                        Ok(())
+                    } else {
+                        Err(nested.error("expected `unordered`"))
+                    }
+                })?;
+            }
+            "derive" => {
+                meta.require_list()?.parse_nested_meta(|nested| {
+                    let Some(ident) = nested.path.get_ident() else {
+                        return Err(nested.error("expected a trait name"));
+                    };
+                    match ident.to_string().as_str() {
+                        "Clone" | "PartialEq" | "Eq" | "Hash" | "PartialOrd" | "Ord" => {
+                            self.derives.push(ident.clone());
+                            Ok(())
+                        }
+                        _ => Err(nested.error(
+                            "expected `Clone`, `PartialEq`, `Eq`, `Hash`, `PartialOrd` or `Ord`",
+                        )),
+                    }
+                })?;
+            }
+            "debug" => {
+                let mut debug = DebugOptions {
+                    span: ident.span(),
+                    style: DebugStyle::Transparent,
+                    max_items: None,
+                    sorted: None,
+                };
+                match meta {
+                    syn::Meta::NameValue(name_value) => {
+                        debug.style = DebugStyle::from_expr(&name_value.value)?;
+                    }
+                    syn::Meta::List(list) => list.parse_nested_meta(|nested| {
+                        if nested.path.is_ident("transparent") {
+                            debug.style = DebugStyle::Transparent;
+                        } else if nested.path.is_ident("named") {
+                            debug.style = DebugStyle::Named;
+                        } else if nested.path.is_ident("max_items") {
+                            let max_items: syn::LitInt = nested.value()?.parse()?;
+                            debug.max_items = Some(max_items.base10_parse()?);
+                        } else if nested.path.is_ident("sorted") {
+                            debug.sorted = Some(nested.path.span());
+                        } else {
+                            return Err(nested.error(
+                                "expected `transparent`, `named`, `max_items` or `sorted`",
+                            ));
+                        }
+                        Ok(())
+                    })?,
+                    syn::Meta::Path(path) => {
+                        return Err(syn::Error::new(
+                            path.span(),
+                            "expected `debug = transparent | named` or `debug(...)`",
+                        ))
+                    }
+                }
+                self.debug = Some(debug);
+            }
+            "display" => {
+                let span = ident.span();
+                let mut display = DisplayOptions {
+                    span,
+                    sep: syn::LitStr::new(", ", span),
+                    prefix: syn::LitStr::new("", span),
+                    suffix: syn::LitStr::new("", span),
+                    kv_sep: syn::LitStr::new("=", span),
+                };
+                if let syn::Meta::List(list) = meta {
+                    list.parse_nested_meta(|nested| {
+                        let field = if nested.path.is_ident("sep") {
+                            &mut display.sep
+                        } else if nested.path.is_ident("prefix") {
+                            &mut display.prefix
+                        } else if nested.path.is_ident("suffix") {
+                            &mut display.suffix
+                        } else if nested.path.is_ident("kv_sep") {
+                            &mut display.kv_sep
+                        } else {
+                            return Err(
+                                nested.error("expected `sep`, `prefix`, `suffix` or `kv_sep`")
+                            );
+                        };
+                        *field = nested.value()?.parse()?;
+                        Ok(())
+                    })?;
+                } else {
+                    meta.require_path_only()?;
+                }
+                self.display = Some(display);
+            }
+            "from_str" => {
+                let span = ident.span();
+                let mut from_str = FromStrOptions {
+                    span,
+                    sep: syn::LitStr::new(",", span),
+                    kv_sep: syn::LitStr::new("=", span),
+                };
+                if let syn::Meta::List(list) = meta {
+                    list.parse_nested_meta(|nested| {
+                        let field = if nested.path.is_ident("sep") {
+                            &mut from_str.sep
+                        } else if nested.path.is_ident("kv_sep") {
+                            &mut from_str.kv_sep
+                        } else {
+                            return Err(nested.error("expected `sep` or `kv_sep`"));
+                        };
+                        *field = nested.value()?.parse()?;
+                        Ok(())
+                    })?;
+                } else {
+                    meta.require_path_only()?;
+                }
+                self.from_str = Some(from_str);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn from_str_span(&self) -> Option<proc_macro2::Span> {
+        self.from_str.as_ref().map(|from_str| from_str.span)
+    }
+
+    /// Returns the option that guarantees an invariant of the collection, which code outside the
+    /// new type's module could break if the field weren't private.
+    fn invariant(&self) -> Option<&'static str> {
+        (self.non_empty.map(|_| "non_empty"))
+            .or(self
+                .min_len
+                .filter(|(_, min_len)| *min_len > 0)
+                .map(|_| "min_len"))
+            .or(self.max_len.map(|_| "max_len"))
+            .or(self.sorted.as_ref().map(|(_, key)| match key {
+                Some(_) => "sorted_by_key",
+                None => "sorted",
+            }))
+            .or(self.unique.map(|_| "unique"))
+    }
+}
+
+impl DebugStyle {
+    fn from_expr(expr: &syn::Expr) -> syn::Result<Self> {
+        let syn::Expr::Path(syn::ExprPath { path, .. }) = expr else {
+            return Err(syn::Error::new(
+                expr.span(),
+                "expected `transparent` or `named`",
+            ));
+        };
+        if path.is_ident("transparent") {
+            Ok(DebugStyle::Transparent)
+        } else if path.is_ident("named") {
+            Ok(DebugStyle::Named)
+        } else {
+            Err(syn::Error::new(
+                path.span(),
+                "expected `transparent` or `named`",
+            ))
+        }
+    }
+}
+
+impl DerefTarget {
+    fn from_expr(expr: &syn::Expr) -> syn::Result<Self> {
+        let syn::Expr::Path(syn::ExprPath { path, .. }) = expr else {
+            return Err(syn::Error::new(expr.span(), "expected `inner` or `slice`"));
+        };
+        if path.is_ident("inner") {
+            Ok(DerefTarget::Inner)
+        } else if path.is_ident("slice") {
+            Ok(DerefTarget::Slice)
+        } else {
+            Err(syn::Error::new(path.span(), "expected `inner` or `slice`"))
+        }
+    }
+}
+
 enum Method {
     Len,
     IsEmpty,
@@ -22,8 +339,15 @@ enum Method {
     Extend,
     New,
     Clear,
+    Entry,
+    Ordered,
+    Membership,
+    SetOps,
+    Hasher,
+    Allocator,
     FromPlural,
     FromInner,
+    FromStd,
     IntoIter,
     FromIter,
     IntoIterRef,
@@ -41,27 +365,113 @@ impl Method {
             "extend" => Ok(Method::Extend),
             "new" => Ok(Method::New),
             "clear" => Ok(Method::Clear),
+            "entry" => Ok(Method::Entry),
+            "ordered" => Ok(Method::Ordered),
+            "membership" => Ok(Method::Membership),
+            "set_ops" => Ok(Method::SetOps),
+            "hasher" => Ok(Method::Hasher),
+            "allocator" => Ok(Method::Allocator),
             "from_plural" => Ok(Method::FromPlural),
             "from_inner" => Ok(Method::FromInner),
+            "from_std" => Ok(Method::FromStd),
             "into_iter" => Ok(Method::IntoIter),
             "from_iter" => Ok(Method::FromIter),
             "into_iter_ref" => Ok(Method::IntoIterRef),
             _ => Err(syn::Error::new(ident.span(), "invalid method")),
         }
     }
+
+    /// Returns `true` for methods that can create an empty collection or empty an existing one.
+    fn may_empty(&self) -> bool {
+        matches!(
+            self,
+            Method::New
+                | Method::Clear
+                | Method::WithCapacity
+                | Method::Entry
+                | Method::Ordered
+                | Method::Membership
+                | Method::SetOps
+                | Method::Hasher
+                | Method::Allocator
+                | Method::FromStd
+        )
+    }
+
+    /// Returns `true` for methods that can add items without checking the length.
+    fn may_grow(&self) -> bool {
+        matches!(
+            self,
+            Method::Extend | Method::Entry | Method::Membership | Method::SetOps | Method::FromStd
+        )
+    }
 }
 
-impl Parse for Methods {
-    fn parse(input: parse::ParseStream) -> syn::Result<Self> {
-        let parsed_methods = input.parse_terminated(syn::Ident::parse, syn::Token![,])?;
+impl Methods {
+    /// Parses methods, collecting options into `options` as they may span several attributes.
+    fn parse(input: parse::ParseStream, options: &mut Options) -> syn::Result<Self> {
+        let parsed_metas = input.parse_terminated(syn::Meta::parse, syn::Token![,])?;
         let mut methods = Vec::new();
-        for ident in parsed_methods {
-            methods.push((ident.clone(), Method::from_ident(&ident)?));
+        for meta in parsed_metas {
+            if options.parse_meta(&meta)? {
+                continue;
+            }
+            let ident = meta.path().require_ident()?;
+            meta.require_path_only()?;
+            methods.push((ident.clone(), Method::from_ident(ident)?));
         }
         Ok(Methods { methods })
     }
 }
 
+/// Wraps items that name the `alloc` crate in an anonymous constant declaring it, so that they
+/// resolve in both std and `no_std` crates, however the collection's path is spelled.
+fn with_alloc(items: TokenStream) -> TokenStream {
+    quote! {
+        const _: () = {
+            extern crate alloc;
+            #items
+        };
+    }
+}
+
+/// Returns `true` if `tokens` contain `ident`, e.g. a new type's name in its field's type.
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(token) => token == *ident,
+        proc_macro2::TokenTree::Group(group) => mentions(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Emits a compile error at each of the `rejected` options that is given together with `option`.
+fn reject_options(option: &str, rejected: &[(Option<proc_macro2::Span>, &str)]) -> TokenStream {
+    let mut errors = TokenStream::new();
+    for &(rejected_span, name) in rejected {
+        if let Some(rejected_span) = rejected_span {
+            let message = format!("`{name}` is not available with `{option}`");
+            errors.extend(quote_spanned! { rejected_span => compile_error!(#message); });
+        }
+    }
+    errors
+}
+
+fn respan_mixed_site(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let proc_macro2::TokenTree::Group(group) = &token {
+                let mut respanned =
+                    proc_macro2::Group::new(group.delimiter(), respan_mixed_site(group.stream()));
+                respanned.set_span(group.span());
+                token = respanned.into();
+            }
+            token.set_span(proc_macro2::Span::mixed_site().located_at(token.span()));
+            token
+        })
+        .collect()
+}
+
 #[proc_macro_derive(Plural, attributes(plural))]
 /// If `#[plural(len, is_empty, iter)]` is specified, only the specified methods will be implemented.
 /// Available methods:
@@ -73,12 +483,66 @@ impl Parse for Methods {
 /// - `with_capacity`
 /// - `new`
 /// - `clear`
-/// - `extend` (provides `impl Extend<ItemType>`)
+/// - `entry` (only for `HashMap`, `BTreeMap` and `IndexMap`; not generated by default when the collection's path starts
+///   with another crate than `std`, `alloc` or `indexmap`, e.g. `hashbrown::HashMap`, as its `Entry` type may differ)
+/// - `ordered` (`range`, `first`, `last`, `pop_first`, `split_off`, etc., only for `BTreeMap` and `BTreeSet`)
+/// - `membership` (`contains`, `get`, `insert`, `remove`, `take`, `replace`, only for `HashSet` and `BTreeSet`)
+/// - `set_ops` (`union`, `intersection`, `difference`, `symmetric_difference` returning `Self`,
+///   `is_subset`, `is_superset`, `is_disjoint`, and the `|`, `&`, `-`, `^` operators, only for `HashSet` and `BTreeSet`)
+/// - `hasher` (`with_hasher`, `with_capacity_and_hasher`, `hasher`, only for `HashMap`, `HashSet` and `IndexMap` with a hasher parameter)
+/// - `allocator` (`new_in`, `with_capacity_in`, `allocator`, only for collections with an allocator parameter such as `Vec<T, A>`,
+///   except for `Box<[T], A>`, which is not supported)
+/// - `extend` (provides `impl Extend<ItemType>`, and `impl Extend<&ItemType>` for `Copy` items)
 /// - `from_inner` (provides `impl From<InnerCollectionType> for NewType`)
+/// - `from_std` (provides `impl From<[ItemType; N]>`, `impl From<&[ItemType]>` and, for `Vec`, `impl From<Box<[ItemType]>>`,
+///   as far as the inner collection supports them)
 /// - `from_plural` (provides `impl From<NewType> for InnerCollectionType`)
 /// - `into_iter` (provides `impl IntoIterator` for `Self`)
-/// - `from_iter` (provides `impl FromIterator<ItemType>`)
+/// - `from_iter` (provides `impl FromIterator<ItemType>`, and `impl FromIterator<&ItemType>` for `Copy` items)
 /// - `into_iter_ref` (provides `impl IntoIterator for &Self`)
+///
+/// The following options can be combined with both the default and the specified methods:
+/// - `deref`, `deref = inner` or `deref = slice` (provides `impl Deref` to the inner collection or `[ItemType]`)
+/// - `deref_mut`, `deref_mut = inner` or `deref_mut = slice` (also provides `impl DerefMut`)
+/// - `as_ref` (provides `impl AsRef<InnerCollectionType>`, and `impl AsRef<[ItemType]>` for slice-backed collections)
+/// - `as_mut` (provides `impl AsMut<InnerCollectionType>`, and `impl AsMut<[ItemType]>` for slice-backed collections)
+/// - `default` (provides `impl Default` bounded on the inner collection instead of the item type)
+/// - `debug = transparent` or `debug = named` (provides `impl Debug` printing `[1, 2]` or `NewType[1, 2]`)
+/// - `debug(max_items = N)`, optionally with `transparent` or `named` (provides `impl Debug` printing only the
+///   first `N` items followed by `… (M more)`)
+/// - `debug(sorted)`, combinable with the above (provides `impl Debug` printing the entries sorted by key, only for
+///   maps and sets)
+/// - `display` or `display(sep = ", ", prefix = "[", suffix = "]", kv_sep = "=")` (provides `impl Display`
+///   using each item's `Display`, with key-value pairs of maps joined by `kv_sep`)
+/// - `from_str` or `from_str(sep = ",", kv_sep = "=")` (provides `impl FromStr` parsing each trimmed item with its own
+///   `FromStr`, failing with a generated `Parse{NewType}Error` that reports the index of the failing item)
+/// - `partial_eq` (provides `impl PartialEq` between the new type and the inner collection in both directions, and
+///   for `Vec`, `VecDeque`, `LinkedList` and `Box<[T]>` also with `[ItemType]`, `&[ItemType]` and `[ItemType; N]`)
+/// - `into_slice` (provides `into_boxed_slice`, `into_arc_slice`, `into_rc_slice`, and `From<NewType>` for `Box<[ItemType]>`,
+///   `Arc<[ItemType]>` and `Rc<[ItemType]>`, only for `Vec`)
+/// - `concat` (provides `Add`, `AddAssign`, `Sum<Self>` and `Extend<Self>` concatenating `Vec`, `VecDeque` and
+///   `LinkedList` new types)
+/// - `derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)` (implements these traits bounded on the inner collection
+///   instead of on every type parameter, unless the inner collection contains the new type itself)
+/// - `hash(unordered)` (provides an order-independent `impl Hash` with the matching `PartialEq` and `Eq`, only for
+///   maps and sets)
+/// - `non_empty` (replaces `impl From<InnerCollectionType>` with `impl TryFrom` and `impl FromIterator` with
+///   `try_from_iter`, both failing with a generated `Empty{NewType}Error`, adds `first` and `last` returning the item
+///   directly for `Vec`, `VecDeque`, `LinkedList`, `Box<[T]>` and `BTreeSet`, and leaves out every method and option
+///   that could create or leave an empty collection, such as `new`, `clear`, `with_capacity`, `entry` and `default`;
+///   the field must be private, and code in the new type's module must not empty it)
+/// - `min_len = M` and `max_len = N` (like `non_empty`, replace the conversions with checked ones failing with a
+///   generated `{NewType}LenError`, and with `max_len` add `try_push` and `try_extend` for `Vec`, `VecDeque`,
+///   `LinkedList` and `BinaryHeap`, leaving out every method and option that could break the bounds; the field must
+///   be private)
+/// - `sorted` or `sorted_by_key = path` (keeps a `Vec` or `VecDeque` sorted, by the items or by the key `path` returns
+///   for them, by sorting in `from_iter`, `From` and `extend`, and provides `insert_sorted` instead of pushing, and
+///   `contains` and `position` using a binary search; the field must be private)
+/// - `unique` (keeps the items of a `Vec` distinct: `from_iter` and `extend` skip duplicates, `push` returns whether
+///   the item was appended, and `impl From<InnerCollectionType>` is replaced with `impl TryFrom` failing with a
+///   generated `Duplicate{NewType}Error` that reports the index of the first duplicate; the field must be private)
+/// - `borrow` (provides `impl Borrow<[ItemType]>` for slice-backed collections; `Hash`, `Eq` and `Ord` of the new
+///   type must agree with those of the slice, which holds for the derived implementations)
 pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let item_struct: ItemStruct = syn::parse_macro_input!(input as ItemStruct);
     let generics = &item_struct.generics;
@@ -106,7 +570,7 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
         return quote_spanned!(item_struct.span() => compile_error!("expected a field")).into();
     };
     let Type::Path(TypePath {
-        path: Path { segments, .. },
+        path: collection_path @ Path { segments, .. },
         ..
     }) = &field.ty
     else {
@@ -115,7 +579,7 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     // last() for ignore paths such as "std::collections::"
     let segment = segments.iter().next_back().unwrap();
     let PathSegment {
-        ident: _collection_name,
+        ident: collection_name,
         arguments: PathArguments::AngleBracketed(arguments),
     } = segment
     else {
@@ -124,16 +588,38 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     if arguments.args.is_empty() {
         return quote_spanned!(segment.span() => compile_error!("failed to get the item type for this collection")).into();
     }
-    let item = if arguments.args.len() >= 2 {
-        let key = &arguments.args[0];
-        let value = &arguments.args[1];
-        Item::KeyValue { key, value }
-    } else {
-        let item = &arguments.args[0];
-        Item::Value(item)
+    let kind = Kind::from_ident(collection_name);
+    let types = arguments
+        .args
+        .iter()
+        .map_while(|argument| match argument {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let item = match types.as_slice() {
+        [Type::Slice(TypeSlice { elem, .. }), ..] if kind == Kind::BoxedSlice => Item::Value(elem),
+        [key, value, ..] if kind.is_map() || (kind == Kind::Other && types.len() >= 2) => {
+            Item::KeyValue { key, value }
+        }
+        [item, ..] => Item::Value(item),
+        [] => {
+            return quote_spanned!(segment.span() => compile_error!("failed to get the item type for this collection")).into();
+        }
     };
+    let hasher = kind
+        .hasher_position()
+        .and_then(|position| arguments.args.get(position));
+    let allocator = kind
+        .allocator_position()
+        .and_then(|position| arguments.args.get(position));
+    // Unlike `Box<[T]>`, `Box<[T], A>` can be neither iterated nor collected into.
+    if let (Kind::BoxedSlice, Some(allocator)) = (kind, allocator) {
+        return quote_spanned!(allocator.span() => compile_error!("`Box<[T], A>` with an allocator is not supported");).into();
+    }
 
     let mut methods = vec![];
+    let mut options = Options::default();
     for attr in item_struct
         .attrs
         .iter()
@@ -145,7 +631,9 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
                 return e.into_compile_error().into();
             }
         };
-        let parsed = match meta.parse_args::<Methods>() {
+        let parsed = match meta
+            .parse_args_with(|input: parse::ParseStream| Methods::parse(input, &mut options))
+        {
             Ok(methods) => methods,
             Err(e) => {
                 return e.into_compile_error().into();
@@ -153,46 +641,116 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
         };
         methods.extend(parsed.methods);
     }
+    if let (Some(option), false) = (
+        options.invariant(),
+        matches!(field.vis, syn::Visibility::Inherited),
+    ) {
+        let message = format!("`{option}` requires the field to be private");
+        return quote_spanned!(field.vis.span() => compile_error!(#message);).into();
+    }
 
     let plural = Plural {
+        vis: &item_struct.vis,
         ident,
         generics,
         generics_without_bounds,
         field_ident,
         collection: &field.ty,
+        collection_path,
+        kind,
         item,
+        hasher,
+        allocator,
+        options,
     };
 
     if methods.is_empty() {
         let span = plural.ident.span();
 
+        let available = |method| plural.unavailable_with(&method).is_none();
+        let len_checked = plural.is_len_checked();
         let from_plural_impl = plural.from(span);
-        let from_inner_impl = plural.from_inner_def(span);
+        let from_inner_impl = if plural.is_from_inner_checked() {
+            plural.try_from_inner(span)
+        } else {
+            plural.from_inner_def(span)
+        };
+        let from_std_impl = if available(Method::FromStd) {
+            plural.from_std(span)
+        } else {
+            TokenStream::new()
+        };
         let into_iter_impl = plural.into_iter(span);
         let into_iter_ref_impl = plural.into_iter_ref(span);
-        let from_iter_impl = plural.from_iter(span);
-        let extend_impl = plural.extend(span);
+        let from_iter_impl = if len_checked {
+            TokenStream::new()
+        } else {
+            plural.from_iter(span)
+        };
+        let extend_impl = if plural.kind.is_growable() && available(Method::Extend) {
+            plural.extend(span)
+        } else {
+            TokenStream::new()
+        };
+        let set_ops_impl = if plural.kind.is_set() && available(Method::SetOps) {
+            plural.set_ops(span)
+        } else {
+            TokenStream::new()
+        };
 
         let mut all_method_definitions = TokenStream::new();
         all_method_definitions.extend(plural.len_def(span));
         all_method_definitions.extend(plural.is_empty_def(span));
         all_method_definitions.extend(plural.iter_def(span));
-        all_method_definitions.extend(plural.capacity_def(span));
-        all_method_definitions.extend(plural.reserve_def(span));
-        all_method_definitions.extend(plural.with_capacity_def(span));
-        all_method_definitions.extend(plural.new_def(span));
-        all_method_definitions.extend(plural.clear_def(span));
+        if plural.kind.has_capacity() {
+            all_method_definitions.extend(plural.capacity_def(span));
+            all_method_definitions.extend(plural.reserve_def(span));
+            if available(Method::WithCapacity) {
+                all_method_definitions.extend(plural.with_capacity_def(span));
+            }
+        }
+        if plural.kind.is_growable() && available(Method::New) {
+            all_method_definitions.extend(plural.new_def(span));
+            all_method_definitions.extend(plural.clear_def(span));
+        }
+        let entry_impl =
+            if plural.kind.is_map() && available(Method::Entry) && plural.has_known_entry() {
+                plural.entry_def(span)
+            } else {
+                TokenStream::new()
+            };
+        if plural.kind.is_ordered() && available(Method::Ordered) {
+            all_method_definitions.extend(plural.ordered_def(span));
+        }
+        if plural.kind.is_set() && available(Method::Membership) {
+            all_method_definitions.extend(plural.membership_def(span));
+            all_method_definitions.extend(plural.set_ops_def(span));
+        }
+        if plural.hasher.is_some() && available(Method::Hasher) {
+            all_method_definitions.extend(plural.hasher_def(span));
+        }
+        if plural.allocator.is_some() && available(Method::Allocator) {
+            all_method_definitions.extend(plural.allocator_def(span));
+        }
+        if len_checked {
+            all_method_definitions.extend(plural.try_from_iter_def(span));
+        }
 
         let delegate_impl = plural.delegate(all_method_definitions);
+        let options_impl = plural.options_impls();
 
         quote! {
             #from_plural_impl
             #from_inner_impl
+            #from_std_impl
             #into_iter_impl
             #into_iter_ref_impl
             #from_iter_impl
             #extend_impl
+            #set_ops_impl
             #delegate_impl
+            #entry_impl
+            #options_impl
         }
         .into()
     } else {
@@ -201,6 +759,11 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
         for (method_ident, method_enum_variant) in methods {
             let span = method_ident.span();
+            if let Some(option) = plural.unavailable_with(&method_enum_variant) {
+                let message = format!("this method is not available with `{option}`");
+                trait_implementations.extend(quote_spanned! { span => compile_error!(#message); });
+                continue;
+            }
             match method_enum_variant {
                 Method::Len => {
                     individual_method_definitions.extend(plural.len_def(span));
@@ -226,18 +789,46 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
                 Method::Clear => {
                     individual_method_definitions.extend(plural.clear_def(span));
                 }
+                Method::Entry => {
+                    trait_implementations.extend(plural.entry_def(span));
+                }
+                Method::Ordered => {
+                    individual_method_definitions.extend(plural.ordered_def(span));
+                }
+                Method::Membership => {
+                    individual_method_definitions.extend(plural.membership_def(span));
+                }
+                Method::SetOps => {
+                    individual_method_definitions.extend(plural.set_ops_def(span));
+                    trait_implementations.extend(plural.set_ops(span));
+                }
+                Method::Hasher => {
+                    individual_method_definitions.extend(plural.hasher_def(span));
+                }
+                Method::Allocator => {
+                    individual_method_definitions.extend(plural.allocator_def(span));
+                }
                 Method::Extend => {
                     trait_implementations.extend(plural.extend(span));
                 }
                 Method::FromPlural => {
                     trait_implementations.extend(plural.from(span));
                 }
+                Method::FromInner if plural.is_from_inner_checked() => {
+                    trait_implementations.extend(plural.try_from_inner(span));
+                }
                 Method::FromInner => {
                     trait_implementations.extend(plural.from_inner_def(span));
                 }
+                Method::FromStd => {
+                    trait_implementations.extend(plural.from_std(span));
+                }
                 Method::IntoIter => {
                     trait_implementations.extend(plural.into_iter(span));
                 }
+                Method::FromIter if plural.is_len_checked() => {
+                    individual_method_definitions.extend(plural.try_from_iter_def(span));
+                }
                 Method::FromIter => {
                     trait_implementations.extend(plural.from_iter(span));
                 }
@@ -252,17 +843,121 @@ pub fn derive_plural(input: proc_macro::TokenStream) -> proc_macro::TokenStream
             final_code.extend(plural.delegate(individual_method_definitions));
         }
         final_code.extend(trait_implementations);
+        final_code.extend(plural.options_impls());
 
         final_code.into()
     }
 }
 
+/// The collection kind, detected from the last segment of the field type's path.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Vec,
+    VecDeque,
+    BinaryHeap,
+    LinkedList,
+    BoxedSlice,
+    HashMap,
+    BTreeMap,
+    IndexMap,
+    HashSet,
+    BTreeSet,
+    Other,
+}
+
+impl Kind {
+    fn from_ident(ident: &Ident) -> Self {
+        match ident.to_string().as_str() {
+            "Vec" => Kind::Vec,
+            "VecDeque" => Kind::VecDeque,
+            "BinaryHeap" => Kind::BinaryHeap,
+            "LinkedList" => Kind::LinkedList,
+            "Box" => Kind::BoxedSlice,
+            "HashMap" => Kind::HashMap,
+            "BTreeMap" => Kind::BTreeMap,
+            "IndexMap" => Kind::IndexMap,
+            "HashSet" => Kind::HashSet,
+            "BTreeSet" => Kind::BTreeSet,
+            _ => Kind::Other,
+        }
+    }
+
+    fn is_map(self) -> bool {
+        matches!(self, Kind::HashMap | Kind::BTreeMap | Kind::IndexMap)
+    }
+
+    fn is_set(self) -> bool {
+        matches!(self, Kind::HashSet | Kind::BTreeSet)
+    }
+
+    /// Returns the position of the `BuildHasher` type argument, e.g. `S` in `HashMap<K, V, S>`.
+    fn hasher_position(self) -> Option<usize> {
+        match self {
+            Kind::HashMap | Kind::IndexMap => Some(2),
+            Kind::HashSet => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Returns the position of the `Allocator` type argument, e.g. `A` in `Vec<T, A>`.
+    fn allocator_position(self) -> Option<usize> {
+        match self {
+            Kind::Vec
+            | Kind::VecDeque
+            | Kind::BinaryHeap
+            | Kind::LinkedList
+            | Kind::BoxedSlice
+            | Kind::BTreeSet => Some(1),
+            Kind::BTreeMap => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Returns the bound that keys must satisfy for lookups, e.g. `Eq + Hash` for hash-based collections.
+    fn lookup_bound(self) -> TokenStream {
+        if self.is_ordered() {
+            quote![Ord]
+        } else {
+            quote![Eq + core::hash::Hash]
+        }
+    }
+
+    /// Returns `true` for collections whose equality depends on the order of the items.
+    fn is_sequence(self) -> bool {
+        matches!(
+            self,
+            Kind::Vec | Kind::VecDeque | Kind::LinkedList | Kind::BoxedSlice
+        )
+    }
+
+    fn is_ordered(self) -> bool {
+        matches!(self, Kind::BTreeMap | Kind::BTreeSet)
+    }
+
+    fn has_capacity(self) -> bool {
+        !self.is_ordered() && !matches!(self, Kind::LinkedList | Kind::BoxedSlice)
+    }
+
+    /// Returns `true` if the collection can be created empty and grown, unlike `Box<[T]>`.
+    fn is_growable(self) -> bool {
+        self != Kind::BoxedSlice
+    }
+
+    /// Returns the module that contains the `Entry` type, and the path to use when the
+    /// collection is not qualified (e.g. `HashMap<K, V>` after `use std::collections::HashMap`).
+    fn entry_module(self) -> Option<(&'static str, TokenStream)> {
+        match self {
+            Kind::HashMap => Some(("hash_map", quote![std::collections::hash_map])),
+            Kind::BTreeMap => Some(("btree_map", quote![alloc::collections::btree_map])),
+            Kind::IndexMap => Some(("map", quote![indexmap::map])),
+            _ => None,
+        }
+    }
+}
+
 enum Item<'a> {
-    KeyValue {
-        key: &'a GenericArgument,
-        value: &'a GenericArgument,
-    },
-    Value(&'a GenericArgument),
+    KeyValue { key: &'a Type, value: &'a Type },
+    Value(&'a Type),
 }
 
 impl Item<'_> {
@@ -285,14 +980,21 @@ impl ToTokens for Item<'_> {
 }
 
 struct Plural<'a> {
+    vis: &'a syn::Visibility,
     ident: &'a Ident,
     generics: &'a Generics,
     generics_without_bounds: Vec<TokenStream>,
     collection: &'a syn::Type,
+    collection_path: &'a Path,
+    kind: Kind,
     field_ident: TokenStream,
     item: Item<'a>,
+    hasher: Option<&'a GenericArgument>,
+    allocator: Option<&'a GenericArgument>,
+    options: Options,
 }
 
+#[allow(clippy::wrong_self_convention)]
 impl Plural<'_> {
     fn into_iter(&self, span: proc_macro2::Span) -> TokenStream {
         let Plural {
@@ -309,7 +1011,8 @@ impl Plural<'_> {
                 type Item = #item_type;
                 type IntoIter = <#collection as IntoIterator>::IntoIter;
                 fn into_iter(self) -> Self::IntoIter {
-                    self.#field_ident.into_iter()
+                    // Fully qualified so that `Box<[T]>` doesn't resolve to the slice's `into_iter`.
+                    IntoIterator::into_iter(self.#field_ident)
                 }
             }
         }
@@ -342,17 +1045,111 @@ impl Plural<'_> {
     }
 
     fn from_iter(&self, span: proc_macro2::Span) -> TokenStream {
+        let mut impls = self.from_iter_owned(span);
+        impls.extend(self.from_iter_ref(span));
+        impls
+    }
+
+    fn from_iter_owned(&self, span: proc_macro2::Span) -> TokenStream {
         let Plural {
             item: item_type,
             ident,
             generics,
             generics_without_bounds,
+            collection,
+            field_ident,
+            kind,
+            allocator,
             ..
         } = self;
+        if self.is_unique() {
+            let unique_bound = self.unique_bound();
+            let (new, allocator_bound) = match allocator {
+                Some(allocator) => (
+                    quote![<#collection>::new_in(Default::default())],
+                    quote![for<'plural> #allocator: Default,],
+                ),
+                None => (quote![<#collection>::new()], TokenStream::new()),
+            };
+            let where_clause = self.where_clause(quote![#allocator_bound #unique_bound]);
+            return quote_spanned! { span =>
+                impl #generics core::iter::FromIterator<#item_type> for #ident<#(#generics_without_bounds,)*> #where_clause {
+                    fn from_iter<I: IntoIterator<Item = #item_type>>(iter: I) -> Self {
+                        let mut collection = #new;
+                        for item in iter {
+                            if !collection.contains(&item) {
+                                collection.push(item);
+                            }
+                        }
+                        #[allow(clippy::init_numbered_fields)]
+                        Self { #field_ident: collection }
+                    }
+                }
+            };
+        }
+        // `FromIterator` is only implemented for the global allocator, so build the collection
+        // in the default allocator and extend it instead.
+        if let (Some(allocator), true) = (allocator, kind.is_growable()) {
+            let sort_bound = self.sort_bound();
+            let where_clause = self.where_clause(quote! {
+                for<'plural> #allocator: Default,
+                #collection: core::iter::Extend<#item_type>,
+                #sort_bound
+            });
+            let sort = self.sort_in_place(quote![collection]);
+            return quote_spanned! { span =>
+                impl #generics core::iter::FromIterator<#item_type> for #ident<#(#generics_without_bounds,)*> #where_clause {
+                    fn from_iter<I: IntoIterator<Item = #item_type>>(iter: I) -> Self {
+                        let mut collection = <#collection>::new_in(Default::default());
+                        collection.extend(iter);
+                        #sort
+                        #[allow(clippy::init_numbered_fields)]
+                        Self { #field_ident: collection }
+                    }
+                }
+            };
+        }
+        let hasher_predicate = self.hasher_predicate(quote![core::hash::BuildHasher + Default]);
+        let sort_bound = self.sort_bound();
+        let where_clause = self.where_clause(quote![#hasher_predicate #sort_bound]);
+        let collection = self.sorted(quote![iter.into_iter().collect()]);
         quote_spanned! { span =>
-            impl #generics core::iter::FromIterator<#item_type> for #ident<#(#generics_without_bounds,)*> {
+            impl #generics core::iter::FromIterator<#item_type> for #ident<#(#generics_without_bounds,)*> #where_clause {
                 fn from_iter<I: IntoIterator<Item = #item_type>>(iter: I) -> Self {
-                    Self(iter.into_iter().collect())
+                    Self(#collection)
+                }
+            }
+        }
+    }
+
+    /// Implements `FromIterator<&T>` whenever the new type supports `Extend<&T>`, i.e. when the
+    /// inner collection does (for `T: Copy` in std).
+    fn from_iter_ref(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            item,
+            ident,
+            generics,
+            generics_without_bounds,
+            ..
+        } = self;
+        let lifetime: GenericParam = parse_quote!('plural);
+        let mut generics = (*generics).to_owned();
+        generics.params.insert(0, lifetime.clone());
+        let item_ref = item.reference(&lifetime);
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        // Bounding on `T: Copy` directly would be rejected for non-`Copy` concrete item types, while
+        // these bounds are not, as they involve a lifetime. `FromIterator<T>` may not hold either,
+        // e.g. for a hasher without `Default`.
+        let where_clause = self.where_clause(quote! {
+            #new_type_full: core::iter::Extend<#item_ref>,
+            for<'plural_from> #new_type_full: core::iter::FromIterator<#item>,
+        });
+        quote_spanned! { span =>
+            impl #generics core::iter::FromIterator<#item_ref> for #new_type_full #where_clause {
+                fn from_iter<I: IntoIterator<Item = #item_ref>>(iter: I) -> Self {
+                    let mut new_type: Self = core::iter::empty::<#item>().collect();
+                    new_type.extend(iter);
+                    new_type
                 }
             }
         }
@@ -385,6 +1182,17 @@ impl Plural<'_> {
             generics_without_bounds,
             ..
         } = self;
+        if self.sort_compare().is_some() {
+            let where_clause = self.where_clause(self.sort_bound());
+            let field = self.sorted(quote![field]);
+            return quote_spanned! { span =>
+                impl #generics From<#collection> for #ident<#(#generics_without_bounds,)*> #where_clause {
+                    fn from(field: #collection) -> Self {
+                        Self(#field)
+                    }
+                }
+            };
+        }
         quote_spanned! { span =>
             impl #generics From<#collection> for #ident<#(#generics_without_bounds,)*> {
                 fn from(field: #collection) -> Self {
@@ -394,19 +1202,138 @@ impl Plural<'_> {
         }
     }
 
+    /// Implements `From` for the std types the inner collection can be converted from. Every bound
+    /// involves a generic parameter, so the impls are allowed even when the inner collection
+    /// doesn't support the conversion.
+    fn from_std(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let sort_bound = self.sort_bound();
+        let converted = self.sorted(quote![<#collection>::from(source)]);
+        let from = |generics: &Generics, source: TokenStream, where_clause: TokenStream| {
+            quote_spanned! { span =>
+                impl #generics From<#source> for #new_type_full #where_clause {
+                    fn from(source: #source) -> Self {
+                        #[allow(clippy::init_numbered_fields)]
+                        Self { #field_ident: #converted }
+                    }
+                }
+            }
+        };
+
+        let mut generics_with_len = (*generics).clone();
+        generics_with_len
+            .params
+            .push(parse_quote!(const PLURAL_LEN: usize));
+        let array = quote![[#item; PLURAL_LEN]];
+        let mut impls = from(
+            &generics_with_len,
+            array.clone(),
+            self.where_clause(quote![#collection: From<#array>, #sort_bound]),
+        );
+
+        let Item::Value(item) = item else {
+            return impls;
+        };
+        if kind.is_set() {
+            return impls;
+        }
+        let lifetime: GenericParam = parse_quote!('plural);
+        let mut generics_with_lifetime = (*generics).clone();
+        generics_with_lifetime.params.insert(0, lifetime.clone());
+        let slice = quote![&#lifetime [#item]];
+        impls.extend(from(
+            &generics_with_lifetime,
+            slice.clone(),
+            self.where_clause(quote![#collection: From<#slice>, #sort_bound]),
+        ));
+        if *kind == Kind::Vec && self.allocator.is_none() {
+            let boxed = quote![alloc::boxed::Box<[#item]>];
+            impls.extend(with_alloc(from(
+                generics,
+                boxed,
+                self.where_clause(sort_bound),
+            )));
+        }
+        impls
+    }
+
     fn extend(&self, span: proc_macro2::Span) -> TokenStream {
         let Plural {
             field_ident,
+            collection,
             item: item_type,
             ident,
             generics,
             generics_without_bounds,
             ..
         } = self;
+        let hasher_predicate = self.hasher_predicate(quote![core::hash::BuildHasher]);
+        let sort_bound = self.sort_bound();
+        let where_clause = self.where_clause(quote![#hasher_predicate #sort_bound]);
+        let sort = self.sort_in_place(quote![self.#field_ident]);
+        // Like std, `Extend<&T>` is only available for `T: Copy`, which the inner collection's
+        // implementation requires. The bound involves the lifetime, so it is allowed even when it
+        // does not hold for concrete types.
+        let lifetime: GenericParam = parse_quote!('plural);
+        let mut generics_with_lifetime = (*generics).to_owned();
+        generics_with_lifetime.params.insert(0, lifetime.clone());
+        let item_ref = item_type.reference(&lifetime);
+        let where_clause_ref = self.where_clause(quote! {
+            #collection: core::iter::Extend<#item_ref>,
+            #sort_bound
+        });
+        if self.is_unique() {
+            let unique_bound = self.unique_bound();
+            let where_clause = self.where_clause(unique_bound.clone());
+            let where_clause_ref = self.where_clause(quote! {
+                #collection: core::iter::Extend<#item_ref>,
+                #unique_bound
+            });
+            return quote_spanned! { span =>
+                impl #generics core::iter::Extend<#item_type> for #ident<#(#generics_without_bounds,)*> #where_clause {
+                    fn extend<I: IntoIterator<Item = #item_type>>(&mut self, iter: I) {
+                        for item in iter {
+                            if !self.#field_ident.contains(&item) {
+                                self.#field_ident.push(item);
+                            }
+                        }
+                    }
+                }
+
+                impl #generics_with_lifetime core::iter::Extend<#item_ref> for #ident<#(#generics_without_bounds,)*> #where_clause_ref {
+                    fn extend<I: IntoIterator<Item = #item_ref>>(&mut self, iter: I) {
+                        for item in iter {
+                            if !self.#field_ident.contains(item) {
+                                // Copies the item, as the inner collection's `Extend<&T>` requires `T: Copy`.
+                                self.#field_ident.extend(core::iter::once(item));
+                            }
+                        }
+                    }
+                }
+            };
+        }
         quote_spanned! { span =>
-            impl #generics core::iter::Extend<#item_type> for #ident<#(#generics_without_bounds,)*> {
+            impl #generics core::iter::Extend<#item_type> for #ident<#(#generics_without_bounds,)*> #where_clause {
                 fn extend<I: IntoIterator<Item = #item_type>>(&mut self, iter: I) {
-                    self.#field_ident.extend(iter)
+                    self.#field_ident.extend(iter);
+                    #sort
+                }
+            }
+
+            impl #generics_with_lifetime core::iter::Extend<#item_ref> for #ident<#(#generics_without_bounds,)*> #where_clause_ref {
+                fn extend<I: IntoIterator<Item = #item_ref>>(&mut self, iter: I) {
+                    self.#field_ident.extend(iter);
+                    #sort
                 }
             }
         }
@@ -462,9 +1389,13 @@ impl Plural<'_> {
     fn reserve_def(&self, method_span: proc_macro2::Span) -> TokenStream {
         let Plural { field_ident, .. } = self;
         let reserve = Ident::new("reserve", method_span);
+        let hasher_predicate = self.hasher_predicate(quote![core::hash::BuildHasher]);
         quote! {
             #[doc = "Reserves capacity for at least `additional` more elements to be inserted in the collection."]
-            pub fn #reserve(&mut self, additional: usize) {
+            pub fn #reserve(&mut self, additional: usize)
+            where
+                #hasher_predicate
+            {
                 self.#field_ident.reserve(additional)
             }
         }
@@ -477,6 +1408,32 @@ impl Plural<'_> {
             ..
         } = self;
         let with_capacity = Ident::new("with_capacity", method_span);
+        // `with_capacity` only exists for the default hasher, so go through the hasher's `Default`.
+        if let Some(hasher) = self.hasher {
+            return quote! {
+                #[doc = "Construct a new empty collection with the specified capacity."]
+                pub fn #with_capacity(capacity: usize) -> Self
+                where
+                    for<'plural> #hasher: Default,
+                {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: <#collection>::with_capacity_and_hasher(capacity, Default::default()) }
+                }
+            };
+        }
+        // Likewise, `with_capacity` only exists for the global allocator.
+        if let Some(allocator) = self.allocator {
+            return quote! {
+                #[doc = "Construct a new empty collection with the specified capacity."]
+                pub fn #with_capacity(capacity: usize) -> Self
+                where
+                    for<'plural> #allocator: Default,
+                {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: <#collection>::with_capacity_in(capacity, Default::default()) }
+                }
+            };
+        }
         quote! {
             #[doc = "Construct a new empty collection with the specified capacity."]
             pub fn #with_capacity(capacity: usize) -> Self {
@@ -493,6 +1450,30 @@ impl Plural<'_> {
             ..
         } = self;
         let new = Ident::new("new", method_span);
+        if let Some(hasher) = self.hasher {
+            return quote! {
+                #[doc = "Creates a new, empty collection."]
+                pub fn #new() -> Self
+                where
+                    for<'plural> #hasher: Default,
+                {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: <#collection>::with_hasher(Default::default()) }
+                }
+            };
+        }
+        if let Some(allocator) = self.allocator {
+            return quote! {
+                #[doc = "Creates a new, empty collection."]
+                pub fn #new() -> Self
+                where
+                    for<'plural> #allocator: Default,
+                {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: <#collection>::new_in(Default::default()) }
+                }
+            };
+        }
         quote! {
             #[doc = "Creates a new, empty collection."]
             pub fn #new() -> Self {
@@ -513,6 +1494,1955 @@ impl Plural<'_> {
         }
     }
 
+    fn entry_def(&self, method_span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            field_ident,
+            collection_path,
+            kind,
+            item,
+            ..
+        } = self;
+        let (Some((module, unqualified)), Item::KeyValue { key, value }) =
+            (kind.entry_module(), item)
+        else {
+            return quote_spanned! { method_span =>
+                compile_error!("`entry` is only available for `HashMap`, `BTreeMap` and `IndexMap`");
+            };
+        };
+        // Reuse the collection's own path so that e.g. `alloc::collections::BTreeMap` resolves to
+        // `alloc::collections::btree_map::Entry`.
+        let segments = &collection_path.segments;
+        let entry_module = if self.is_qualified() {
+            let leading_colon = &collection_path.leading_colon;
+            let prefix = segments
+                .iter()
+                .take(segments.len() - 1)
+                .map(|segment| &segment.ident);
+            let module = Ident::new(module, method_span);
+            quote![#leading_colon #(#prefix::)* #module]
+        } else {
+            unqualified
+        };
+        let entry = Ident::new("entry", method_span);
+        let bound = kind.lookup_bound();
+        let hasher_predicate = self.hasher_predicate(quote![core::hash::BuildHasher]);
+        with_alloc(self.delegate(quote! {
+            #[doc = "Gets the given key's corresponding entry in the map for in-place manipulation."]
+            pub fn #entry(&mut self, key: #key) -> #entry_module::Entry<'_, #key, #value>
+            where
+                #key: #bound,
+                #hasher_predicate
+            {
+                self.#field_ident.entry(key)
+            }
+        }))
+    }
+
+    fn ordered_def(&self, method_span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let range = Ident::new("range", method_span);
+        let pop_first = Ident::new("pop_first", method_span);
+        let pop_last = Ident::new("pop_last", method_span);
+        let split_off = Ident::new("split_off", method_span);
+        let (key, specific) = match (kind, item) {
+            (Kind::BTreeMap, Item::KeyValue { key, value }) => {
+                let range_mut = Ident::new("range_mut", method_span);
+                let first_key_value = Ident::new("first_key_value", method_span);
+                let last_key_value = Ident::new("last_key_value", method_span);
+                let specific = quote! {
+                    /// Iterates over a sub-range of entries, with mutable references to the values.
+                    pub fn #range_mut<Q, R>(&mut self, range: R) -> impl DoubleEndedIterator<Item = (&#key, &mut #value)>
+                    where
+                        #key: core::borrow::Borrow<Q> + Ord,
+                        Q: Ord + ?Sized,
+                        R: core::ops::RangeBounds<Q>,
+                    {
+                        self.#field_ident.range_mut(range)
+                    }
+
+                    #[doc = "Returns the first key-value pair in the map."]
+                    pub fn #first_key_value(&self) -> Option<(&#key, &#value)>
+                    where
+                        #key: Ord,
+                    {
+                        self.#field_ident.first_key_value()
+                    }
+
+                    #[doc = "Returns the last key-value pair in the map."]
+                    pub fn #last_key_value(&self) -> Option<(&#key, &#value)>
+                    where
+                        #key: Ord,
+                    {
+                        self.#field_ident.last_key_value()
+                    }
+                };
+                (*key, specific)
+            }
+            (Kind::BTreeSet, Item::Value(key)) => {
+                let first = Ident::new("first", method_span);
+                let last = Ident::new("last", method_span);
+                let specific = quote! {
+                    #[doc = "Returns the first element in the set."]
+                    pub fn #first(&self) -> Option<&#key>
+                    where
+                        #key: Ord,
+                    {
+                        self.#field_ident.first()
+                    }
+
+                    #[doc = "Returns the last element in the set."]
+                    pub fn #last(&self) -> Option<&#key>
+                    where
+                        #key: Ord,
+                    {
+                        self.#field_ident.last()
+                    }
+                };
+                (*key, specific)
+            }
+            _ => {
+                return quote_spanned! { method_span =>
+                    compile_error!("`ordered` is only available for `BTreeMap` and `BTreeSet`");
+                };
+            }
+        };
+        let reference = item.reference(quote! {});
+        quote! {
+            /// Iterates over a sub-range of the collection.
+            pub fn #range<Q, R>(&self, range: R) -> impl DoubleEndedIterator<Item = #reference>
+            where
+                #key: core::borrow::Borrow<Q> + Ord,
+                Q: Ord + ?Sized,
+                R: core::ops::RangeBounds<Q>,
+            {
+                self.#field_ident.range(range)
+            }
+
+            #specific
+
+            #[doc = "Removes and returns the first element in the collection."]
+            pub fn #pop_first(&mut self) -> Option<#item>
+            where
+                #key: Ord,
+            {
+                self.#field_ident.pop_first()
+            }
+
+            #[doc = "Removes and returns the last element in the collection."]
+            pub fn #pop_last(&mut self) -> Option<#item>
+            where
+                #key: Ord,
+            {
+                self.#field_ident.pop_last()
+            }
+
+            #[doc = "Splits the collection into two at the given key, returning everything after and including the key."]
+            pub fn #split_off<Q>(&mut self, key: &Q) -> Self
+            where
+                #key: core::borrow::Borrow<Q> + Ord,
+                Q: Ord + ?Sized,
+            {
+                #[allow(clippy::init_numbered_fields)]
+                Self { #field_ident: self.#field_ident.split_off(key) }
+            }
+        }
+    }
+
+    fn membership_def(&self, method_span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let (true, Item::Value(item)) = (kind.is_set(), item) else {
+            return quote_spanned! { method_span =>
+                compile_error!("`membership` is only available for `HashSet` and `BTreeSet`");
+            };
+        };
+        let bound = kind.lookup_bound();
+        let hasher_predicate = self.hasher_predicate(quote![core::hash::BuildHasher]);
+        let contains = Ident::new("contains", method_span);
+        let get = Ident::new("get", method_span);
+        let insert = Ident::new("insert", method_span);
+        let remove = Ident::new("remove", method_span);
+        let take = Ident::new("take", method_span);
+        let replace = Ident::new("replace", method_span);
+        quote! {
+            #[doc = "Returns `true` if the set contains the value."]
+            pub fn #contains<Q>(&self, value: &Q) -> bool
+            where
+                #item: core::borrow::Borrow<Q> + #bound,
+                Q: #bound + ?Sized,
+                #hasher_predicate
+            {
+                self.#field_ident.contains(value)
+            }
+
+            #[doc = "Returns a reference to the value in the set that is equal to the given value."]
+            pub fn #get<Q>(&self, value: &Q) -> Option<&#item>
+            where
+                #item: core::borrow::Borrow<Q> + #bound,
+                Q: #bound + ?Sized,
+                #hasher_predicate
+            {
+                self.#field_ident.get(value)
+            }
+
+            #[doc = "Adds a value to the set, returning `false` if it was already present."]
+            pub fn #insert(&mut self, value: #item) -> bool
+            where
+                #item: #bound,
+                #hasher_predicate
+            {
+                self.#field_ident.insert(value)
+            }
+
+            #[doc = "Removes a value from the set, returning `true` if it was present."]
+            pub fn #remove<Q>(&mut self, value: &Q) -> bool
+            where
+                #item: core::borrow::Borrow<Q> + #bound,
+                Q: #bound + ?Sized,
+                #hasher_predicate
+            {
+                self.#field_ident.remove(value)
+            }
+
+            #[doc = "Removes and returns the value in the set that is equal to the given value."]
+            pub fn #take<Q>(&mut self, value: &Q) -> Option<#item>
+            where
+                #item: core::borrow::Borrow<Q> + #bound,
+                Q: #bound + ?Sized,
+                #hasher_predicate
+            {
+                self.#field_ident.take(value)
+            }
+
+            #[doc = "Adds a value to the set, replacing and returning the existing equal value, if any."]
+            pub fn #replace(&mut self, value: #item) -> Option<#item>
+            where
+                #item: #bound,
+                #hasher_predicate
+            {
+                self.#field_ident.replace(value)
+            }
+        }
+    }
+
+    fn allocator_def(&self, method_span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            field_ident,
+            collection,
+            kind,
+            allocator,
+            ..
+        } = self;
+        let Some(allocator) = allocator else {
+            return quote_spanned! { method_span =>
+                compile_error!("`allocator` requires a collection with an allocator parameter, e.g. `Vec<T, A>`");
+            };
+        };
+        let new_in = Ident::new("new_in", method_span);
+        let with_capacity_in = Ident::new("with_capacity_in", method_span);
+        let allocator_fn = Ident::new("allocator", method_span);
+        let mut methods = TokenStream::new();
+        if kind.is_growable() {
+            methods.extend(quote! {
+                #[doc = "Creates a new, empty collection in the given allocator."]
+                pub fn #new_in(alloc: #allocator) -> Self {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: <#collection>::new_in(alloc) }
+                }
+            });
+        }
+        if kind.has_capacity() {
+            methods.extend(quote! {
+                #[doc = "Creates a new, empty collection with the specified capacity in the given allocator."]
+                pub fn #with_capacity_in(capacity: usize, alloc: #allocator) -> Self {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: <#collection>::with_capacity_in(capacity, alloc) }
+                }
+            });
+        }
+        if matches!(kind, Kind::Vec | Kind::VecDeque | Kind::BinaryHeap) {
+            methods.extend(quote! {
+                #[doc = "Returns a reference to the underlying allocator."]
+                pub fn #allocator_fn(&self) -> &#allocator {
+                    <#collection>::allocator(&self.#field_ident)
+                }
+            });
+        }
+        methods
+    }
+
+    fn hasher_def(&self, method_span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            field_ident,
+            collection,
+            hasher,
+            ..
+        } = self;
+        let Some(hasher) = hasher else {
+            return quote_spanned! { method_span =>
+                compile_error!("`hasher` requires a collection with a hasher parameter, e.g. `HashMap<K, V, S>`");
+            };
+        };
+        let with_hasher = Ident::new("with_hasher", method_span);
+        let with_capacity_and_hasher = Ident::new("with_capacity_and_hasher", method_span);
+        let hasher_fn = Ident::new("hasher", method_span);
+        quote! {
+            #[doc = "Creates a new, empty collection which will use the given hash builder."]
+            pub fn #with_hasher(hash_builder: #hasher) -> Self {
+                #[allow(clippy::init_numbered_fields)]
+                Self { #field_ident: <#collection>::with_hasher(hash_builder) }
+            }
+
+            #[doc = "Creates a new, empty collection with the specified capacity, using the given hash builder."]
+            pub fn #with_capacity_and_hasher(capacity: usize, hash_builder: #hasher) -> Self {
+                #[allow(clippy::init_numbered_fields)]
+                Self { #field_ident: <#collection>::with_capacity_and_hasher(capacity, hash_builder) }
+            }
+
+            #[doc = "Returns a reference to the collection's hash builder."]
+            pub fn #hasher_fn(&self) -> &#hasher {
+                self.#field_ident.hasher()
+            }
+        }
+    }
+
+    fn set_ops_def(&self, method_span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let (true, Item::Value(item)) = (kind.is_set(), item) else {
+            return quote_spanned! { method_span =>
+                compile_error!("`set_ops` is only available for `HashSet` and `BTreeSet`");
+            };
+        };
+        let bound = kind.lookup_bound();
+        let hasher_predicate = self.hasher_predicate(quote![core::hash::BuildHasher]);
+        let hasher_predicate_collect =
+            self.hasher_predicate(quote![core::hash::BuildHasher + Default]);
+        let combinators = [
+            ("union", "Returns the values that are in `self` or in `other`."),
+            ("intersection", "Returns the values that are both in `self` and `other`."),
+            ("difference", "Returns the values that are in `self` but not in `other`."),
+            (
+                "symmetric_difference",
+                "Returns the values that are in `self` or in `other`, but not in both.",
+            ),
+        ]
+        .map(|(name, doc)| {
+            let name = Ident::new(name, method_span);
+            // The binder keeps `Clone` from being rejected as a trivially false bound for concrete
+            // item types that aren't `Clone`, so that the other methods are still available.
+            quote! {
+                #[doc = #doc]
+                pub fn #name(&self, other: &Self) -> Self
+                where
+                    for<'plural> #item: Clone + #bound,
+                    #hasher_predicate_collect
+                {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: self.#field_ident.#name(&other.#field_ident).cloned().collect() }
+                }
+            }
+        });
+        let predicates = [
+            (
+                "is_subset",
+                "Returns `true` if `other` contains at least all the values in `self`.",
+            ),
+            (
+                "is_superset",
+                "Returns `true` if `self` contains at least all the values in `other`.",
+            ),
+            (
+                "is_disjoint",
+                "Returns `true` if `self` has no values in common with `other`.",
+            ),
+        ]
+        .map(|(name, doc)| {
+            let name = Ident::new(name, method_span);
+            quote! {
+                #[doc = #doc]
+                pub fn #name(&self, other: &Self) -> bool
+                where
+                    #item: #bound,
+                    #hasher_predicate
+                {
+                    self.#field_ident.#name(&other.#field_ident)
+                }
+            }
+        });
+        quote! {
+            #(#combinators)*
+            #(#predicates)*
+        }
+    }
+
+    fn set_ops(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let (true, Item::Value(item)) = (kind.is_set(), item) else {
+            return quote_spanned! { span =>
+                compile_error!("`set_ops` is only available for `HashSet` and `BTreeSet`");
+            };
+        };
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let bound = kind.lookup_bound();
+        let hasher_predicate = self.hasher_predicate(quote![core::hash::BuildHasher]);
+        let hasher_predicate_collect =
+            self.hasher_predicate(quote![core::hash::BuildHasher + Default]);
+        let where_clause = self.where_clause(quote![#item: #bound, #hasher_predicate]);
+        // As in `set_ops_def`, the binder allows the impls for concrete items that aren't `Clone`.
+        let where_clause_clone =
+            self.where_clause(quote![for<'plural> #item: Clone + #bound, #hasher_predicate]);
+        let where_clause_collect = self
+            .where_clause(quote![for<'plural> #item: Clone + #bound, #hasher_predicate_collect]);
+        let retain_contained = quote! {
+            self.#field_ident.retain(|item| rhs.#field_ident.contains(item))
+        };
+        let retain_not_contained = quote! {
+            self.#field_ident.retain(|item| !rhs.#field_ident.contains(item))
+        };
+        let ops = [
+            (
+                quote![BitOr],
+                quote![bitor],
+                quote![BitOrAssign],
+                quote![bitor_assign],
+                quote![union],
+                quote! { self.#field_ident.extend(rhs.#field_ident) },
+                quote! { self.#field_ident.extend(rhs.#field_ident.iter().cloned()) },
+            ),
+            (
+                quote![BitAnd],
+                quote![bitand],
+                quote![BitAndAssign],
+                quote![bitand_assign],
+                quote![intersection],
+                retain_contained.clone(),
+                retain_contained,
+            ),
+            (
+                quote![Sub],
+                quote![sub],
+                quote![SubAssign],
+                quote![sub_assign],
+                quote![difference],
+                retain_not_contained.clone(),
+                retain_not_contained,
+            ),
+            (
+                quote![BitXor],
+                quote![bitxor],
+                quote![BitXorAssign],
+                quote![bitxor_assign],
+                quote![symmetric_difference],
+                quote! {
+                    for item in rhs.#field_ident {
+                        if !self.#field_ident.remove(&item) {
+                            self.#field_ident.insert(item);
+                        }
+                    }
+                },
+                quote! {
+                    for item in &rhs.#field_ident {
+                        if !self.#field_ident.remove(item) {
+                            self.#field_ident.insert(item.clone());
+                        }
+                    }
+                },
+            ),
+        ];
+        ops.into_iter()
+            .map(|(op, op_fn, op_assign, op_assign_fn, method, assign_owned, assign_ref)| {
+                quote_spanned! { span =>
+                    impl #generics core::ops::#op<&#new_type_full> for &#new_type_full #where_clause_collect {
+                        type Output = #new_type_full;
+                        fn #op_fn(self, rhs: &#new_type_full) -> Self::Output {
+                            self.#method(rhs)
+                        }
+                    }
+
+                    impl #generics core::ops::#op for #new_type_full #where_clause {
+                        type Output = Self;
+                        fn #op_fn(mut self, rhs: Self) -> Self::Output {
+                            core::ops::#op_assign::#op_assign_fn(&mut self, rhs);
+                            self
+                        }
+                    }
+
+                    impl #generics core::ops::#op_assign for #new_type_full #where_clause {
+                        fn #op_assign_fn(&mut self, rhs: Self) {
+                            #assign_owned
+                        }
+                    }
+
+                    impl #generics core::ops::#op_assign<&#new_type_full> for #new_type_full #where_clause_clone {
+                        fn #op_assign_fn(&mut self, rhs: &Self) {
+                            #assign_ref
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn options_impls(&self) -> TokenStream {
+        let mut impls = TokenStream::new();
+        let len_option = (self.options.non_empty)
+            .or(self.options.min_len.map(|(span, _)| span))
+            .or(self.options.max_len.map(|(span, _)| span));
+        // `min_len = 0` alone bounds nothing, so it leaves the new type unchanged.
+        if let Some(span) = len_option.filter(|_| self.is_len_checked()) {
+            impls.extend(self.len_bounds(span));
+        }
+        if let Some((span, _)) = self.options.sorted {
+            impls.extend(self.sorted_def(span));
+        }
+        if let Some(span) = self.options.unique {
+            impls.extend(self.unique_def(span));
+        }
+        if let Some((span, target)) = self.options.deref {
+            impls.extend(self.deref(span, target));
+        }
+        if let Some(span) = self.options.as_ref {
+            impls.extend(self.as_ref(span));
+        }
+        if let Some(span) = self.options.as_mut {
+            impls.extend(self.as_mut(span));
+        }
+        if let Some(span) = self.options.borrow {
+            impls.extend(self.borrow(span));
+        }
+        if let Some(span) = self.options.default {
+            impls.extend(self.default(span));
+        }
+        if let Some(debug) = &self.options.debug {
+            impls.extend(self.debug(debug));
+        }
+        if let Some(display) = &self.options.display {
+            impls.extend(self.display(display));
+        }
+        if let Some(from_str) = &self.options.from_str {
+            impls.extend(self.from_str(from_str));
+        }
+        if let Some(span) = self.options.partial_eq {
+            impls.extend(self.partial_eq(span));
+        }
+        if let Some(span) = self.options.into_slice {
+            impls.extend(self.into_slice(span));
+        }
+        if let Some(span) = self.options.concat {
+            impls.extend(self.concat(span));
+        }
+        if let Some(span) = self.options.hash_unordered {
+            impls.extend(self.hash_unordered(span));
+        }
+        for derive in &self.options.derives {
+            impls.extend(self.derive(derive));
+        }
+        impls
+    }
+
+    /// Returns `[T]` if the collection dereferences to a slice.
+    fn slice(&self) -> Option<TokenStream> {
+        match (self.kind, &self.item) {
+            (Kind::Vec | Kind::BoxedSlice, Item::Value(item)) => Some(quote![[#item]]),
+            _ => None,
+        }
+    }
+
+    fn as_ref(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            ..
+        } = self;
+        let where_clause = self.where_clause(TokenStream::new());
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let as_ref_slice = self.slice().map(|slice| {
+            quote_spanned! { span =>
+                impl #generics AsRef<#slice> for #new_type_full #where_clause {
+                    fn as_ref(&self) -> &#slice {
+                        &self.#field_ident
+                    }
+                }
+            }
+        });
+        quote_spanned! { span =>
+            impl #generics AsRef<#collection> for #new_type_full #where_clause {
+                fn as_ref(&self) -> &#collection {
+                    &self.#field_ident
+                }
+            }
+
+            #as_ref_slice
+        }
+    }
+
+    fn as_mut(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            ..
+        } = self;
+        let where_clause = self.where_clause(TokenStream::new());
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let as_mut_slice = self.slice().map(|slice| {
+            quote_spanned! { span =>
+                impl #generics AsMut<#slice> for #new_type_full #where_clause {
+                    fn as_mut(&mut self) -> &mut #slice {
+                        &mut self.#field_ident
+                    }
+                }
+            }
+        });
+        quote_spanned! { span =>
+            impl #generics AsMut<#collection> for #new_type_full #where_clause {
+                fn as_mut(&mut self) -> &mut #collection {
+                    &mut self.#field_ident
+                }
+            }
+
+            #as_mut_slice
+        }
+    }
+
+    fn default(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            ..
+        } = self;
+        // Bound on the collection rather than on the type parameters, unlike `#[derive(Default)]`.
+        let where_clause = self.where_clause(quote![#collection: Default]);
+        quote_spanned! { span =>
+            impl #generics Default for #ident<#(#generics_without_bounds,)*> #where_clause {
+                fn default() -> Self {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self { #field_ident: Default::default() }
+                }
+            }
+        }
+    }
+
+    fn debug(&self, debug: &DebugOptions) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            ..
+        } = self;
+        let name = match debug.style {
+            DebugStyle::Transparent => None,
+            DebugStyle::Named => {
+                let name = ident.to_string();
+                Some(quote! { f.write_str(#name)?; })
+            }
+        };
+        if debug.max_items.is_some() || debug.sorted.is_some() {
+            return self.debug_entries(debug, name);
+        }
+        let where_clause = self.where_clause(quote![#collection: core::fmt::Debug]);
+        quote_spanned! { debug.span =>
+            impl #generics core::fmt::Debug for #ident<#(#generics_without_bounds,)*> #where_clause {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    #name
+                    core::fmt::Debug::fmt(&self.#field_ident, f)
+                }
+            }
+        }
+    }
+
+    /// Prints the items one by one, keeping the brackets of the collection kind so that it reads
+    /// like the inner collection's `Debug`. With `max_items`, only the first items are printed,
+    /// followed by `… (M more)`.
+    fn debug_entries(&self, debug: &DebugOptions, name: Option<TokenStream>) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let sorted = debug.sorted.is_some();
+        if let Some(span) = debug.sorted {
+            if !(kind.is_map() || kind.is_set() || matches!(item, Item::KeyValue { .. })) {
+                return quote_spanned! { span =>
+                    compile_error!("`debug(sorted)` is only available for maps and sets");
+                };
+            }
+        }
+        let (bounds, builder, sort, pair) = match item {
+            Item::KeyValue { key, value } => (
+                if sorted {
+                    quote![#key: core::fmt::Debug + Ord, #value: core::fmt::Debug]
+                } else {
+                    quote![#key: core::fmt::Debug, #value: core::fmt::Debug]
+                },
+                quote![debug_set],
+                quote![entries.sort_unstable_by(|a, b| a.0.cmp(b.0));],
+                Some(quote! {
+                    struct Pair<'a, K, V>(&'a K, &'a V);
+                    impl<K: core::fmt::Debug, V: core::fmt::Debug> core::fmt::Debug for Pair<'_, K, V> {
+                        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                            core::fmt::Debug::fmt(self.0, f)?;
+                            f.write_str(": ")?;
+                            core::fmt::Debug::fmt(self.1, f)
+                        }
+                    }
+                }),
+            ),
+            Item::Value(item) => (
+                if sorted {
+                    quote![#item: core::fmt::Debug + Ord]
+                } else {
+                    quote![#item: core::fmt::Debug]
+                },
+                if kind.is_set() {
+                    quote![debug_set]
+                } else {
+                    quote![debug_list]
+                },
+                quote![entries.sort_unstable();],
+                None,
+            ),
+        };
+        let mut entries = if sorted {
+            quote! {{
+                extern crate alloc;
+                let mut entries: alloc::vec::Vec<_> = self.#field_ident.iter().collect();
+                #sort
+                entries.into_iter()
+            }}
+        } else {
+            quote![self.#field_ident.iter()]
+        };
+        if let Some(max_items) = debug.max_items {
+            entries = quote![#entries.take(#max_items)];
+        }
+        if pair.is_some() {
+            entries = quote![#entries.map(|(key, value)| Pair(key, value))];
+        }
+        let more = debug.max_items.map(|max_items| {
+            quote! {
+                struct More(usize);
+                impl core::fmt::Debug for More {
+                    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(f, "\u{2026} ({} more)", self.0)
+                    }
+                }
+                let len = self.#field_ident.len();
+                if len > #max_items {
+                    builder.entry(&More(len - #max_items));
+                }
+            }
+        });
+        let where_clause = self.where_clause(bounds);
+        quote_spanned! { debug.span =>
+            impl #generics core::fmt::Debug for #ident<#(#generics_without_bounds,)*> #where_clause {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    #pair
+                    #name
+                    let mut builder = f.#builder();
+                    builder.entries(#entries);
+                    #more
+                    builder.finish()
+                }
+            }
+        }
+    }
+
+    fn display(&self, display: &DisplayOptions) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            field_ident,
+            item,
+            ..
+        } = self;
+        let DisplayOptions {
+            span,
+            sep,
+            prefix,
+            suffix,
+            kv_sep,
+        } = display;
+        let (bounds, write_item) = match item {
+            Item::KeyValue { key, value } => (
+                quote![#key: core::fmt::Display, #value: core::fmt::Display],
+                quote! {
+                    let (key, value) = item;
+                    core::fmt::Display::fmt(key, f)?;
+                    f.write_str(#kv_sep)?;
+                    core::fmt::Display::fmt(value, f)?;
+                },
+            ),
+            Item::Value(item) => (
+                quote![#item: core::fmt::Display],
+                quote! {
+                    core::fmt::Display::fmt(item, f)?;
+                },
+            ),
+        };
+        let where_clause = self.where_clause(bounds);
+        quote_spanned! { *span =>
+            impl #generics core::fmt::Display for #ident<#(#generics_without_bounds,)*> #where_clause {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.write_str(#prefix)?;
+                    for (index, item) in self.#field_ident.iter().enumerate() {
+                        if index > 0 {
+                            f.write_str(#sep)?;
+                        }
+                        #write_item
+                    }
+                    f.write_str(#suffix)
+                }
+            }
+        }
+    }
+
+    /// Implements `FromStr` along with a `Parse{NewType}Error` type next to the new type, as the
+    /// error type cannot live in this proc-macro crate.
+    fn from_str(&self, from_str: &FromStrOptions) -> TokenStream {
+        let Plural {
+            vis,
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            item,
+            ..
+        } = self;
+        let FromStrOptions { span, sep, kv_sep } = from_str;
+        let error = Ident::new(&format!("Parse{ident}Error"), ident.span());
+        let (error_def, error_type, bounds, parse_item) = match item {
+            Item::KeyValue { key, value } => {
+                let doc = format!("An error returned when parsing [`{ident}`] from a string.");
+                let error_def = quote! {
+                    #[doc = #doc]
+                    #[derive(Debug, Clone, PartialEq, Eq)]
+                    #vis enum #error<K, V> {
+                        #[doc = "The item at `index` has no key-value separator."]
+                        MissingSeparator { index: usize },
+                        #[doc = "The key of the item at `index` failed to parse."]
+                        Key { index: usize, error: K },
+                        #[doc = "The value of the item at `index` failed to parse."]
+                        Value { index: usize, error: V },
+                    }
+
+                    impl<K, V> #error<K, V> {
+                        #[doc = "Returns the index of the item that failed to parse."]
+                        pub fn index(&self) -> usize {
+                            match self {
+                                Self::MissingSeparator { index }
+                                | Self::Key { index, .. }
+                                | Self::Value { index, .. } => *index,
+                            }
+                        }
+                    }
+
+                    impl<K: core::fmt::Display, V: core::fmt::Display> core::fmt::Display for #error<K, V> {
+                        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                            match self {
+                                Self::MissingSeparator { index } => {
+                                    write!(f, "missing {:?} in item {}", #kv_sep, index)
+                                }
+                                Self::Key { index, error } => {
+                                    write!(f, "failed to parse the key of item {}: {}", index, error)
+                                }
+                                Self::Value { index, error } => {
+                                    write!(f, "failed to parse the value of item {}: {}", index, error)
+                                }
+                            }
+                        }
+                    }
+
+                    impl<K: core::fmt::Debug + core::fmt::Display, V: core::fmt::Debug + core::fmt::Display> core::error::Error for #error<K, V> {}
+                };
+                let error_type = quote! {
+                    #error<<#key as core::str::FromStr>::Err, <#value as core::str::FromStr>::Err>
+                };
+                let bounds = quote! {
+                    #key: core::str::FromStr,
+                    #value: core::str::FromStr,
+                    #collection: core::iter::FromIterator<(#key, #value)>,
+                };
+                let parse_item = quote! {
+                    let (key, value) = item
+                        .split_once(#kv_sep)
+                        .ok_or(#error::MissingSeparator { index })?;
+                    let key = key.trim().parse().map_err(|error| #error::Key { index, error })?;
+                    let value = value.trim().parse().map_err(|error| #error::Value { index, error })?;
+                    Ok((key, value))
+                };
+                (error_def, error_type, bounds, parse_item)
+            }
+            Item::Value(item) => {
+                let doc = format!("An error returned when parsing [`{ident}`] from a string.");
+                let error_def = quote! {
+                    #[doc = #doc]
+                    #[derive(Debug, Clone, PartialEq, Eq)]
+                    #vis struct #error<E> {
+                        #[doc = "The index of the item that failed to parse."]
+                        pub index: usize,
+                        #[doc = "The error returned by the item's `FromStr`."]
+                        pub error: E,
+                    }
+
+                    impl<E: core::fmt::Display> core::fmt::Display for #error<E> {
+                        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                            write!(f, "failed to parse item {}: {}", self.index, self.error)
+                        }
+                    }
+
+                    impl<E: core::fmt::Debug + core::fmt::Display> core::error::Error for #error<E> {}
+                };
+                let error_type = quote! { #error<<#item as core::str::FromStr>::Err> };
+                let bounds = quote! {
+                    #item: core::str::FromStr,
+                    #collection: core::iter::FromIterator<#item>,
+                };
+                let parse_item = quote! {
+                    item.trim().parse().map_err(|error| #error { index, error })
+                };
+                (error_def, error_type, bounds, parse_item)
+            }
+        };
+        let where_clause = self.where_clause(bounds);
+        quote_spanned! { *span =>
+            #error_def
+
+            impl #generics core::str::FromStr for #ident<#(#generics_without_bounds,)*> #where_clause {
+                type Err = #error_type;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    let collection = if s.trim().is_empty() {
+                        core::iter::empty().collect()
+                    } else {
+                        s.split(#sep)
+                            .enumerate()
+                            .map(|(index, item)| { #parse_item })
+                            .collect::<Result<#collection, _>>()?
+                    };
+                    #[allow(clippy::init_numbered_fields)]
+                    Ok(Self { #field_ident: collection })
+                }
+            }
+        }
+    }
+
+    /// Implements `PartialEq` in both directions between the new type and the inner collection,
+    /// and for sequences also slices and arrays.
+    fn partial_eq(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let where_clause = self.where_clause(quote![#collection: PartialEq]);
+        let mut impls = quote_spanned! { span =>
+            impl #generics PartialEq<#collection> for #new_type_full #where_clause {
+                fn eq(&self, other: &#collection) -> bool {
+                    self.#field_ident == *other
+                }
+            }
+
+            impl #generics PartialEq<#new_type_full> for #collection #where_clause {
+                fn eq(&self, other: &#new_type_full) -> bool {
+                    *self == other.#field_ident
+                }
+            }
+        };
+        let (true, Item::Value(item)) = (kind.is_sequence(), item) else {
+            return impls;
+        };
+        let where_clause = self.where_clause(quote![#item: PartialEq]);
+        let mut generics_with_len = (*generics).clone();
+        generics_with_len
+            .params
+            .push(parse_quote!(const PLURAL_LEN: usize));
+        for (generics, other) in [
+            (*generics, quote![[#item]]),
+            (*generics, quote![&[#item]]),
+            (&generics_with_len, quote![[#item; PLURAL_LEN]]),
+        ] {
+            impls.extend(quote_spanned! { span =>
+                impl #generics PartialEq<#other> for #new_type_full #where_clause {
+                    fn eq(&self, other: &#other) -> bool {
+                        self.#field_ident.len() == other.len() && self.#field_ident.iter().eq(other.iter())
+                    }
+                }
+
+                impl #generics PartialEq<#new_type_full> for #other #where_clause {
+                    fn eq(&self, other: &#new_type_full) -> bool {
+                        self.len() == other.#field_ident.len() && self.iter().eq(other.#field_ident.iter())
+                    }
+                }
+            });
+        }
+        impls
+    }
+
+    /// Implements conversions of `Vec`-backed new types into boxed and shared slices.
+    fn into_slice(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            field_ident,
+            kind,
+            item,
+            allocator,
+            ..
+        } = self;
+        let (Kind::Vec, Item::Value(item), None) = (kind, item, allocator) else {
+            return quote_spanned! { span =>
+                compile_error!("`into_slice` is only available for `Vec` with the global allocator");
+            };
+        };
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let where_clause = self.where_clause(TokenStream::new());
+        let methods = [
+            (
+                "into_boxed_slice",
+                quote![boxed::Box],
+                "Converts the collection into `Box<[T]>`, dropping any excess capacity.",
+            ),
+            (
+                "into_arc_slice",
+                quote![sync::Arc],
+                "Converts the collection into `Arc<[T]>` to share it across threads.",
+            ),
+            (
+                "into_rc_slice",
+                quote![rc::Rc],
+                "Converts the collection into `Rc<[T]>`.",
+            ),
+        ]
+        .map(|(name, pointer, doc)| {
+            let name = Ident::new(name, span);
+            quote! {
+                #[doc = #doc]
+                pub fn #name(self) -> alloc::#pointer<[#item]> {
+                    self.#field_ident.into()
+                }
+            }
+        });
+        let from_impls = [quote![boxed::Box], quote![sync::Arc], quote![rc::Rc]].map(|pointer| {
+            quote_spanned! { span =>
+                impl #generics From<#new_type_full> for alloc::#pointer<[#item]> #where_clause {
+                    fn from(new_type_instance: #new_type_full) -> Self {
+                        new_type_instance.#field_ident.into()
+                    }
+                }
+            }
+        });
+        let delegate_impl = self.delegate(quote! { #(#methods)* });
+        with_alloc(quote! {
+            #delegate_impl
+            #(#from_impls)*
+        })
+    }
+
+    /// Implements concatenation of sequences through the inner collection's `Extend`, like the
+    /// `extend` delegation.
+    fn concat(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        if !(kind.is_sequence() && kind.is_growable()) {
+            return quote_spanned! { span =>
+                compile_error!("`concat` is only available for `Vec`, `VecDeque` and `LinkedList`");
+            };
+        }
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let where_clause = self.where_clause(quote![#collection: core::iter::Extend<#item>]);
+        let where_clause_sum =
+            self.where_clause(quote![#collection: core::iter::Extend<#item> + Default]);
+        let mut impls = quote_spanned! { span =>
+            impl #generics core::iter::Extend<#new_type_full> for #new_type_full #where_clause {
+                fn extend<I: IntoIterator<Item = #new_type_full>>(&mut self, iter: I) {
+                    for other in iter {
+                        self.#field_ident.extend(other.#field_ident);
+                    }
+                }
+            }
+
+            impl #generics core::ops::AddAssign for #new_type_full #where_clause {
+                fn add_assign(&mut self, rhs: Self) {
+                    self.#field_ident.extend(rhs.#field_ident);
+                }
+            }
+
+            impl #generics core::ops::Add for #new_type_full #where_clause {
+                type Output = Self;
+                fn add(mut self, rhs: Self) -> Self::Output {
+                    self.#field_ident.extend(rhs.#field_ident);
+                    self
+                }
+            }
+        };
+        // The sum of no collections is empty.
+        if self.min_len().is_some() {
+            return impls;
+        }
+        impls.extend(quote_spanned! { span =>
+            impl #generics core::iter::Sum for #new_type_full #where_clause_sum {
+                fn sum<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+                    // Reuse the first collection's allocation.
+                    let Some(mut first) = iter.next() else {
+                        #[allow(clippy::init_numbered_fields)]
+                        return Self { #field_ident: Default::default() };
+                    };
+                    core::iter::Extend::extend(&mut first, iter);
+                    first
+                }
+            }
+        });
+        impls
+    }
+
+    /// Implements a `Hash` that doesn't depend on the iteration order, by summing a hash of each
+    /// item, along with `PartialEq` and `Eq` through the inner collection to match it.
+    fn hash_unordered(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            kind,
+            item,
+            ..
+        } = self;
+        let bounds = match item {
+            Item::KeyValue { key, value } => {
+                quote![#key: core::hash::Hash, #value: core::hash::Hash]
+            }
+            Item::Value(item) if kind.is_set() => quote![#item: core::hash::Hash],
+            Item::Value(_) => {
+                return quote_spanned! { span =>
+                    compile_error!("`hash(unordered)` is only available for maps and sets");
+                };
+            }
+        };
+        // Each item is hashed with `DefaultHasher`, which only exists in std.
+        if (self.collection_path.segments.first()).is_some_and(|segment| segment.ident == "alloc") {
+            return quote_spanned! { span =>
+                compile_error!("`hash(unordered)` requires std");
+            };
+        }
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let where_clause = self.where_clause(bounds);
+        let where_clause_eq = self.where_clause(quote![#collection: PartialEq]);
+        let where_clause_eq_total = self.where_clause(quote![#collection: Eq]);
+        quote_spanned! { span =>
+            impl #generics core::hash::Hash for #new_type_full #where_clause {
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    let mut sum: u64 = 0;
+                    for item in self.#field_ident.iter() {
+                        // `DefaultHasher::new` uses fixed keys, so equal items hash equally.
+                        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                        core::hash::Hash::hash(&item, &mut hasher);
+                        sum = sum.wrapping_add(core::hash::Hasher::finish(&hasher));
+                    }
+                    state.write_usize(self.#field_ident.len());
+                    state.write_u64(sum);
+                }
+            }
+
+            impl #generics PartialEq for #new_type_full #where_clause_eq {
+                fn eq(&self, other: &Self) -> bool {
+                    self.#field_ident == other.#field_ident
+                }
+            }
+
+            impl #generics Eq for #new_type_full #where_clause_eq_total {}
+        }
+    }
+
+    /// Implements a standard trait like `#[derive(...)]` would, but bounded on the inner collection
+    /// implementing it instead of on every type parameter. For recursive new types such as
+    /// `Tree(Vec<Tree>)`, that bound would require the impl being defined, so they fall back to
+    /// bounding the type parameters like `#[derive(...)]`.
+    fn derive(&self, derive: &Ident) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            ..
+        } = self;
+        let span = derive.span();
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        let trait_path = match derive.to_string().as_str() {
+            "Clone" => quote![Clone],
+            "PartialEq" => quote![PartialEq],
+            "Eq" => quote![Eq],
+            "Hash" => quote![core::hash::Hash],
+            "PartialOrd" => quote![PartialOrd],
+            _ => quote![Ord],
+        };
+        if self.options.hash_unordered.is_some()
+            && matches!(derive.to_string().as_str(), "PartialEq" | "Eq" | "Hash")
+        {
+            return quote_spanned! { span =>
+                compile_error!("already implemented by `hash(unordered)`");
+            };
+        }
+        let body = match derive.to_string().as_str() {
+            "Clone" => quote! {
+                fn clone(&self) -> Self {
+                    #[allow(clippy::init_numbered_fields)]
+                    Self {
+                        #field_ident: Clone::clone(&self.#field_ident),
+                    }
+                }
+            },
+            "PartialEq" => quote! {
+                fn eq(&self, other: &Self) -> bool {
+                    self.#field_ident == other.#field_ident
+                }
+            },
+            "Eq" => TokenStream::new(),
+            "Hash" => quote! {
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    core::hash::Hash::hash(&self.#field_ident, state)
+                }
+            },
+            "PartialOrd" => quote! {
+                fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                    PartialOrd::partial_cmp(&self.#field_ident, &other.#field_ident)
+                }
+            },
+            _ => quote! {
+                fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                    Ord::cmp(&self.#field_ident, &other.#field_ident)
+                }
+            },
+        };
+        let bounds = if mentions(collection.to_token_stream(), ident) {
+            let params = generics.type_params().map(|param| &param.ident);
+            quote![#(#params: #trait_path,)*]
+        } else {
+            quote![#collection: #trait_path]
+        };
+        let where_clause = self.where_clause(bounds);
+        quote_spanned! { span =>
+            impl #generics #trait_path for #new_type_full #where_clause {
+                #body
+            }
+        }
+    }
+
+    /// Returns the minimum length required by `non_empty` or `min_len`, with the option's name.
+    fn min_len(&self) -> Option<(usize, &'static str)> {
+        match (self.options.non_empty, self.options.min_len) {
+            (Some(_), _) => Some((1, "non_empty")),
+            (None, Some((_, min_len))) if min_len > 0 => Some((min_len, "min_len")),
+            _ => None,
+        }
+    }
+
+    fn max_len(&self) -> Option<usize> {
+        self.options.max_len.map(|(_, max_len)| max_len)
+    }
+
+    /// Returns `true` if `From` and `FromIterator` are replaced by their checked counterparts.
+    fn is_len_checked(&self) -> bool {
+        self.min_len().is_some() || self.max_len().is_some()
+    }
+
+    /// Returns the name of the option that rules out `method`, if any.
+    fn unavailable_with(&self, method: &Method) -> Option<&'static str> {
+        match self.min_len() {
+            Some((_, option)) if method.may_empty() => Some(option),
+            _ if self.max_len().is_some() && method.may_grow() => Some("max_len"),
+            _ if self.is_unique() && matches!(method, Method::FromStd) => Some("unique"),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `From<InnerCollectionType>` is replaced by `TryFrom`.
+    fn is_from_inner_checked(&self) -> bool {
+        self.is_len_checked() || self.is_unique()
+    }
+
+    /// Returns `true` for `unique` new types, which must be backed by a `Vec`.
+    fn is_unique(&self) -> bool {
+        self.options.unique.is_some() && self.kind == Kind::Vec
+    }
+
+    /// Returns the bound required to compare the items of a `unique` new type.
+    fn unique_bound(&self) -> TokenStream {
+        match (self.is_unique(), &self.item) {
+            (true, Item::Value(item)) => quote![#item: Eq,],
+            _ => TokenStream::new(),
+        }
+    }
+
+    /// Returns the error type of the checked conversions, and the check of a `collection` variable
+    /// returning early with it.
+    fn conversion_check(&self) -> (Ident, TokenStream) {
+        let ident = self.ident;
+        if self.is_unique() {
+            let error = Ident::new(&format!("Duplicate{ident}Error"), ident.span());
+            let check = quote! {
+                for (index, item) in collection.iter().enumerate() {
+                    if collection[..index].contains(item) {
+                        return Err(#error { index });
+                    }
+                }
+            };
+            return (error, check);
+        }
+        if self.options.non_empty.is_some() {
+            let error = Ident::new(&format!("Empty{ident}Error"), ident.span());
+            let check = quote! {
+                if collection.is_empty() {
+                    return Err(#error);
+                }
+            };
+            return (error, check);
+        }
+        let error = Ident::new(&format!("{ident}LenError"), ident.span());
+        let min_check = self.min_len().map(|(min_len, _)| {
+            quote! {
+                if collection.len() < #min_len {
+                    return Err(#error::TooShort { len: collection.len() });
+                }
+            }
+        });
+        let max_check = self.max_len().map(|max_len| {
+            quote! {
+                if collection.len() > #max_len {
+                    return Err(#error::TooLong { len: collection.len() });
+                }
+            }
+        });
+        (error, quote! { #min_check #max_check })
+    }
+
+    /// Generates the error of the checked conversions, together with the infallible `first` and
+    /// `last` of non-empty new types and the checked `try_push` and `try_extend` of bounded ones,
+    /// and rejects the options that could break the bounds.
+    fn len_bounds(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            vis,
+            ident,
+            collection,
+            field_ident,
+            kind,
+            item,
+            options,
+            ..
+        } = self;
+        if let (Some(_), Some((option_span, _))) =
+            (options.non_empty, options.min_len.or(options.max_len))
+        {
+            return quote_spanned! { option_span =>
+                compile_error!("`non_empty` can't be combined with `min_len` or `max_len`, use `min_len = 1` instead");
+            };
+        }
+        if let (Some((_, min_len)), Some((max_span, max_len))) = (options.min_len, options.max_len)
+        {
+            if min_len > max_len {
+                return quote_spanned! { max_span =>
+                    compile_error!("`max_len` must not be less than `min_len`");
+                };
+            }
+        }
+
+        let mut rejected = vec![
+            (options.deref_mut, "deref_mut"),
+            (options.as_mut, "as_mut"),
+            (options.from_str_span(), "from_str"),
+        ];
+        if self.min_len().is_some() {
+            rejected.push((options.default, "default"));
+        }
+        if self.max_len().is_some() {
+            rejected.push((options.concat, "concat"));
+        }
+        let option = match self.min_len() {
+            Some((_, option)) => option,
+            None => "max_len",
+        };
+        let mut impls = reject_options(option, &rejected);
+
+        let (error, _) = self.conversion_check();
+        if options.non_empty.is_some() {
+            let doc =
+                format!("The error returned when creating [`{ident}`] from an empty collection.");
+            let message = format!("{ident} must not be empty");
+            impls.extend(quote_spanned! { span =>
+                #[doc = #doc]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                #vis struct #error;
+
+                impl core::fmt::Display for #error {
+                    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        f.write_str(#message)
+                    }
+                }
+
+                impl core::error::Error for #error {}
+            });
+        } else {
+            let doc = format!(
+                "The error returned when [`{ident}`] would have fewer or more items than allowed."
+            );
+            let too_short = format!(
+                "{ident} must have at least {} items, got {{}}",
+                self.min_len().map_or(0, |(min_len, _)| min_len)
+            );
+            let too_long = format!(
+                "{ident} must have at most {} items, got {{}}",
+                self.max_len().unwrap_or(usize::MAX)
+            );
+            impls.extend(quote_spanned! { span =>
+                #[doc = #doc]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                #vis enum #error {
+                    #[doc = "The collection would have `len` items, fewer than `min_len`."]
+                    TooShort { len: usize },
+                    #[doc = "The collection would have `len` items, more than `max_len`."]
+                    TooLong { len: usize },
+                }
+
+                impl core::fmt::Display for #error {
+                    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        match self {
+                            Self::TooShort { len } => write!(f, #too_short, len),
+                            Self::TooLong { len } => write!(f, #too_long, len),
+                        }
+                    }
+                }
+
+                impl core::error::Error for #error {}
+            });
+        }
+
+        let mut methods = TokenStream::new();
+        if self.min_len().is_some() {
+            let first_last = match kind {
+                Kind::Vec | Kind::BoxedSlice | Kind::BTreeSet => {
+                    Some((quote![first], quote![last]))
+                }
+                Kind::VecDeque | Kind::LinkedList => Some((quote![front], quote![back])),
+                _ => None,
+            };
+            if let Some((first, last)) = first_last {
+                let first_ident = Ident::new("first", span);
+                let last_ident = Ident::new("last", span);
+                methods.extend(quote_spanned! { span =>
+                    #[doc = "Returns the first item, which always exists."]
+                    pub fn #first_ident(&self) -> &#item {
+                        self.#field_ident.#first().expect("the collection is not empty")
+                    }
+
+                    #[doc = "Returns the last item, which always exists."]
+                    pub fn #last_ident(&self) -> &#item {
+                        self.#field_ident.#last().expect("the collection is not empty")
+                    }
+                });
+            }
+        }
+        if let Some(max_len) = self.max_len() {
+            let push = match kind {
+                Kind::Vec | Kind::BinaryHeap => Some(quote![push]),
+                Kind::VecDeque | Kind::LinkedList => Some(quote![push_back]),
+                _ => None,
+            };
+            if let Some(push) = push {
+                let bounds = if *kind == Kind::BinaryHeap {
+                    quote![#item: Ord,]
+                } else {
+                    self.sort_bound()
+                };
+                // Keep `sorted` new types sorted.
+                let (push, sort) = if self.sort_compare().is_some() {
+                    let insert_sorted = Ident::new("insert_sorted", span);
+                    (
+                        quote![self.#insert_sorted(item);],
+                        self.sort_in_place(quote![self.#field_ident]),
+                    )
+                } else {
+                    (quote![self.#field_ident.#push(item);], TokenStream::new())
+                };
+                let try_push = Ident::new("try_push", span);
+                let try_extend = Ident::new("try_extend", span);
+                methods.extend(quote_spanned! { span =>
+                    #[doc = "Adds an item, failing without changes if the collection is full."]
+                    pub fn #try_push(&mut self, item: #item) -> Result<(), #error>
+                    where
+                        #bounds
+                    {
+                        let len = self.#field_ident.len() + 1;
+                        if len > #max_len {
+                            return Err(#error::TooLong { len });
+                        }
+                        #push
+                        Ok(())
+                    }
+
+                    #[doc = "Adds the items of an iterator, failing without changes if they don't all fit."]
+                    pub fn #try_extend<I: IntoIterator<Item = #item>>(&mut self, iter: I) -> Result<(), #error>
+                    where
+                        #bounds
+                        for<'plural_from> #collection: FromIterator<#item>,
+                    {
+                        let items: #collection = iter.into_iter().collect();
+                        let len = self.#field_ident.len() + items.len();
+                        if len > #max_len {
+                            return Err(#error::TooLong { len });
+                        }
+                        self.#field_ident.extend(items);
+                        #sort
+                        Ok(())
+                    }
+                });
+            }
+        }
+        if !methods.is_empty() {
+            impls.extend(self.delegate(methods));
+        }
+        impls
+    }
+
+    /// Implements `TryFrom<InnerCollectionType>`, failing when the collection's length is out of
+    /// bounds.
+    fn try_from_inner(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            collection,
+            ident,
+            generics,
+            generics_without_bounds,
+            field_ident,
+            ..
+        } = self;
+        let (error, check) = self.conversion_check();
+        let sort_bound = self.sort_bound();
+        let unique_bound = self.unique_bound();
+        let where_clause = self.where_clause(quote![#sort_bound #unique_bound]);
+        let collection_sorted = self.sorted(quote![collection]);
+        quote_spanned! { span =>
+            impl #generics TryFrom<#collection> for #ident<#(#generics_without_bounds,)*> #where_clause {
+                type Error = #error;
+
+                fn try_from(collection: #collection) -> Result<Self, Self::Error> {
+                    #check
+                    #[allow(clippy::init_numbered_fields)]
+                    Ok(Self { #field_ident: #collection_sorted })
+                }
+            }
+        }
+    }
+
+    /// Defines `try_from_iter`, which replaces `FromIterator` for new types with length bounds.
+    fn try_from_iter_def(&self, method_span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            item,
+            collection,
+            field_ident,
+            kind,
+            allocator,
+            ..
+        } = self;
+        let (error, check) = self.conversion_check();
+        let sort_bound = self.sort_bound();
+        let collection_sorted = self.sorted(quote![collection]);
+        let try_from_iter = Ident::new("try_from_iter", method_span);
+        let (bounds, collect) = match (allocator, kind.is_growable()) {
+            (Some(allocator), true) => (
+                quote! {
+                    for<'plural> #allocator: Default,
+                    #collection: core::iter::Extend<#item>,
+                },
+                quote! {
+                    let mut collection = <#collection>::new_in(Default::default());
+                    collection.extend(iter);
+                },
+            ),
+            _ => (
+                self.hasher_predicate(quote![core::hash::BuildHasher + Default]),
+                quote! {
+                    let collection: #collection = iter.into_iter().collect();
+                },
+            ),
+        };
+        quote! {
+            #[doc = "Creates the collection from an iterator, failing if the number of items is out of bounds."]
+            pub fn #try_from_iter<I: IntoIterator<Item = #item>>(iter: I) -> Result<Self, #error>
+            where
+                #bounds
+                #sort_bound
+            {
+                #collect
+                #check
+                #[allow(clippy::init_numbered_fields)]
+                Ok(Self { #field_ident: #collection_sorted })
+            }
+        }
+    }
+
+    /// Returns a closure comparing two items of a `sorted` new type, and the bound it requires.
+    fn sort_compare(&self) -> Option<(TokenStream, TokenStream)> {
+        let (_, key) = self.options.sorted.as_ref()?;
+        let (Kind::Vec | Kind::VecDeque, Item::Value(item)) = (self.kind, &self.item) else {
+            return None;
+        };
+        Some(match key {
+            Some(key) => (
+                quote! { |a: &#item, b: &#item| Ord::cmp(&(#key)(a), &(#key)(b)) },
+                TokenStream::new(),
+            ),
+            None => (
+                quote! { |a: &#item, b: &#item| Ord::cmp(a, b) },
+                quote![#item: Ord,],
+            ),
+        })
+    }
+
+    fn sort_bound(&self) -> TokenStream {
+        self.sort_compare()
+            .map(|(_, bound)| bound)
+            .unwrap_or_default()
+    }
+
+    /// Sorts the collection at `place` if the new type is `sorted`.
+    fn sort_in_place(&self, place: TokenStream) -> TokenStream {
+        let Some((compare, _)) = self.sort_compare() else {
+            return TokenStream::new();
+        };
+        let slice = if self.kind == Kind::VecDeque {
+            quote![#place.make_contiguous()]
+        } else {
+            place
+        };
+        // Stable, and linear for the already sorted prefix when extending.
+        quote! { #slice.sort_by(#compare); }
+    }
+
+    /// Wraps the `collection` expression to sort it if the new type is `sorted`.
+    fn sorted(&self, collection: TokenStream) -> TokenStream {
+        if self.sort_compare().is_none() {
+            return collection;
+        }
+        let ty = self.collection;
+        let sort = self.sort_in_place(quote![collection]);
+        quote! {{
+            let mut collection: #ty = #collection;
+            #sort
+            collection
+        }}
+    }
+
+    /// Defines `insert_sorted` and the binary searching `contains` and `position` of `sorted` new
+    /// types, and rejects the options that could unsort them.
+    fn sorted_def(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            field_ident,
+            item,
+            options,
+            ..
+        } = self;
+        let Some((compare, bound)) = self.sort_compare() else {
+            return quote_spanned! { span =>
+                compile_error!("`sorted` is only available for `Vec` and `VecDeque`");
+            };
+        };
+        // Items with equal keys aren't necessarily equal, so the search compares them with `==` too.
+        // `Ord` already implies `PartialEq` when sorting by the items themselves.
+        let eq_bound = match options.sorted {
+            Some((_, Some(_))) => quote![for<'plural_eq> #item: PartialEq,],
+            _ => bound.clone(),
+        };
+        let mut impls = reject_options(
+            "sorted",
+            &[
+                (options.deref_mut, "deref_mut"),
+                (options.as_mut, "as_mut"),
+                (options.concat, "concat"),
+                (options.from_str_span(), "from_str"),
+            ],
+        );
+
+        let insert_sorted = Ident::new("insert_sorted", span);
+        let contains = Ident::new("contains", span);
+        let position = Ident::new("position", span);
+        impls.extend(self.delegate(quote_spanned! { span =>
+            #[doc = "Inserts an item after the equal ones, keeping the collection sorted, and returns its index."]
+            pub fn #insert_sorted(&mut self, item: #item) -> usize
+            where
+                #bound
+            {
+                let compare = #compare;
+                let index = self
+                    .#field_ident
+                    .partition_point(|probe| compare(probe, &item) != core::cmp::Ordering::Greater);
+                self.#field_ident.insert(index, item);
+                index
+            }
+
+            #[doc = "Returns `true` if the collection contains an item equal to the given one, using a binary search."]
+            pub fn #contains(&self, item: &#item) -> bool
+            where
+                #eq_bound
+            {
+                self.#position(item).is_some()
+            }
+
+            #[doc = "Returns the index of the first item equal to the given one, using a binary search."]
+            pub fn #position(&self, item: &#item) -> Option<usize>
+            where
+                #eq_bound
+            {
+                let compare = #compare;
+                let start = self
+                    .#field_ident
+                    .partition_point(|probe| compare(probe, item) == core::cmp::Ordering::Less);
+                self.#field_ident
+                    .iter()
+                    .skip(start)
+                    .take_while(|probe| compare(probe, item) == core::cmp::Ordering::Equal)
+                    .position(|probe| probe == item)
+                    .map(|offset| start + offset)
+            }
+        }));
+        impls
+    }
+
+    /// Generates the error of `TryFrom` and the `push` of `unique` new types, and rejects the
+    /// options that could add duplicates.
+    fn unique_def(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            vis,
+            ident,
+            field_ident,
+            kind,
+            item,
+            options,
+            ..
+        } = self;
+        let (Kind::Vec, Item::Value(item)) = (kind, item) else {
+            return quote_spanned! { span =>
+                compile_error!("`unique` is only available for `Vec`");
+            };
+        };
+        let conflicting = (options.sorted.as_ref())
+            .map(|(span, _)| *span)
+            .or(options.non_empty)
+            .or(options.min_len.map(|(span, _)| span))
+            .or(options.max_len.map(|(span, _)| span));
+        if let Some(conflicting_span) = conflicting {
+            return quote_spanned! { conflicting_span =>
+                compile_error!("`unique` can't be combined with `sorted`, `non_empty`, `min_len` or `max_len`");
+            };
+        }
+        let mut impls = reject_options(
+            "unique",
+            &[
+                (options.deref_mut, "deref_mut"),
+                (options.as_mut, "as_mut"),
+                (options.concat, "concat"),
+                (options.from_str_span(), "from_str"),
+            ],
+        );
+
+        let (error, _) = self.conversion_check();
+        let doc = format!(
+            "The error returned when creating [`{ident}`] from a collection with duplicates."
+        );
+        impls.extend(quote_spanned! { span =>
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #vis struct #error {
+                #[doc = "The index of the first item equal to an earlier one."]
+                pub index: usize,
+            }
+
+            impl core::fmt::Display for #error {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    write!(f, "duplicate item at index {}", self.index)
+                }
+            }
+
+            impl core::error::Error for #error {}
+        });
+
+        let push = Ident::new("push", span);
+        impls.extend(self.delegate(quote_spanned! { span =>
+            #[doc = "Appends an item unless an equal one is present, returning whether it was appended."]
+            pub fn #push(&mut self, item: #item) -> bool
+            where
+                #item: Eq,
+            {
+                if self.#field_ident.contains(&item) {
+                    return false;
+                }
+                self.#field_ident.push(item);
+                true
+            }
+        }));
+        impls
+    }
+
+    /// Returns `true` if the collection's path names its module, e.g. `std::collections::HashMap`.
+    fn is_qualified(&self) -> bool {
+        self.collection_path.segments.len() >= 2
+    }
+
+    /// Returns `true` unless the collection's path starts with a crate whose `Entry` type may take
+    /// more than the key and value types, such as `hashbrown`, so that `entry` can be generated by
+    /// default. Like `Kind::entry_module`, an unqualified `HashMap` is assumed to be std's.
+    fn has_known_entry(&self) -> bool {
+        !self.is_qualified()
+            || self
+                .collection_path
+                .segments
+                .first()
+                .is_some_and(|segment| {
+                    segment.ident == "std"
+                        || segment.ident == "alloc"
+                        || segment.ident == "indexmap"
+                })
+    }
+
+    fn borrow(&self, span: proc_macro2::Span) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            field_ident,
+            ..
+        } = self;
+        let Some(slice) = self.slice() else {
+            return quote_spanned! { span =>
+                compile_error!("`borrow` is only available for slice-backed collections such as `Vec` and `Box<[T]>`");
+            };
+        };
+        let where_clause = self.where_clause(TokenStream::new());
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        quote_spanned! { span =>
+            impl #generics core::borrow::Borrow<#slice> for #new_type_full #where_clause {
+                fn borrow(&self) -> &#slice {
+                    &self.#field_ident
+                }
+            }
+        }
+    }
+
+    fn deref(&self, span: proc_macro2::Span, target: DerefTarget) -> TokenStream {
+        let Plural {
+            ident,
+            generics,
+            generics_without_bounds,
+            collection,
+            field_ident,
+            options,
+            ..
+        } = self;
+        let target = match (target, self.slice()) {
+            (DerefTarget::Inner, _) => quote![#collection],
+            (DerefTarget::Slice, Some(slice)) => slice,
+            (DerefTarget::Slice, None) => {
+                return quote_spanned! { span =>
+                    compile_error!("`deref = slice` is only available for slice-backed collections such as `Vec` and `Box<[T]>`");
+                };
+            }
+        };
+        let where_clause = self.where_clause(TokenStream::new());
+        let new_type_full = quote! { #ident<#(#generics_without_bounds,)*> };
+        // Deref coercion turns `&Vec<T>` into `&[T]` for the slice target.
+        let deref_mut = options.deref_mut.map(|span| {
+            quote_spanned! { span =>
+                impl #generics core::ops::DerefMut for #new_type_full #where_clause {
+                    fn deref_mut(&mut self) -> &mut Self::Target {
+                        &mut self.#field_ident
+                    }
+                }
+            }
+        });
+        quote_spanned! { span =>
+            impl #generics core::ops::Deref for #new_type_full #where_clause {
+                type Target = #target;
+                fn deref(&self) -> &Self::Target {
+                    &self.#field_ident
+                }
+            }
+
+            #deref_mut
+        }
+    }
+
+    /// Returns `S: bounds,` for the collection's hasher parameter, if any. The binder keeps e.g.
+    /// `Default` from being rejected as a trivially false bound for a concrete hasher without it.
+    fn hasher_predicate(&self, bounds: TokenStream) -> TokenStream {
+        match self.hasher {
+            Some(hasher) => quote! { for<'plural> #hasher: #bounds, },
+            None => TokenStream::new(),
+        }
+    }
+
+    /// Builds a where clause from the struct's own predicates followed by `bounds`.
+    fn where_clause(&self, bounds: TokenStream) -> TokenStream {
+        let predicates = self
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| where_clause.predicates.iter());
+        quote! { where #(#predicates,)* #bounds }
+    }
+
     fn delegate(&self, content: TokenStream) -> TokenStream {
         let Plural {
             ident,
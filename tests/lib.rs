@@ -4,7 +4,7 @@ use thisisplural::Plural;
 
 #[test]
 fn example() {
-    // This implements `From`, `Into`, `FromIterator`, `IntoIterator`, `Deref`, and `DerefMut`.
+    // This implements `From`, `Into`, `FromIterator`, and `IntoIterator`.
     #[derive(Plural)]
     struct Numbers(Vec<u32>);
 
@@ -216,3 +216,930 @@ fn into_iter_ref() {
 #[derive(Plural, Debug, PartialEq)]
 #[plural(len, is_empty, iter, new, clear)]
 struct BTreeMapTuple(std::collections::BTreeMap<u8, bool>);
+
+#[test]
+fn hash_map_entry() {
+    let mut counts = HashMapTuple(HashMap::new());
+    *counts.entry(1).or_insert(false) = true;
+    counts.entry(2).or_insert_with(|| false);
+    assert_eq!(counts, HashMapTuple(HashMap::from([(1, true), (2, false)])));
+
+    #[derive(Plural)]
+    struct Unqualified(HashMap<u8, u8>);
+
+    let mut unqualified = Unqualified::new();
+    *unqualified.entry(1).or_default() += 2;
+    assert_eq!(unqualified.0, HashMap::from([(1, 2)]));
+}
+
+#[test]
+fn btree_map_entry() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(entry)]
+    struct Counts(std::collections::BTreeMap<&'static str, usize>);
+
+    let mut counts = Counts(Default::default());
+    for word in ["a", "b", "a"] {
+        *counts.entry(word).or_default() += 1;
+    }
+    assert_eq!(
+        counts,
+        Counts(std::collections::BTreeMap::from([("a", 2), ("b", 1)]))
+    );
+}
+
+#[test]
+fn index_map_entry() {
+    use indexmap::IndexMap;
+
+    #[derive(Plural, Debug, PartialEq)]
+    struct Groups(IndexMap<bool, Vec<u8>>);
+
+    let mut groups = Groups::new();
+    for n in [1, 2, 3] {
+        groups.entry(n % 2 == 0).or_default().push(n);
+    }
+    assert_eq!(
+        groups,
+        Groups(IndexMap::from([(false, vec![1, 3]), (true, vec![2])]))
+    );
+}
+
+#[test]
+fn btree_map_ordered() {
+    #[derive(Plural, Debug, PartialEq)]
+    struct Scores(std::collections::BTreeMap<u8, char>);
+
+    let mut scores = Scores::from_iter([(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+    assert_eq!(
+        scores.range(2..4).collect::<Vec<_>>(),
+        vec![(&2, &'b'), (&3, &'c')]
+    );
+    for (_, value) in scores.range_mut(..=1) {
+        *value = 'z';
+    }
+    assert_eq!(scores.first_key_value(), Some((&1, &'z')));
+    assert_eq!(scores.last_key_value(), Some((&4, &'d')));
+    let upper = scores.split_off(&3);
+    assert_eq!(upper, Scores::from_iter([(3, 'c'), (4, 'd')]));
+    assert_eq!(scores.pop_first(), Some((1, 'z')));
+    assert_eq!(scores.pop_last(), Some((2, 'b')));
+    assert!(scores.is_empty());
+}
+
+#[test]
+fn btree_set_ordered() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(ordered)]
+    struct Words(std::collections::BTreeSet<String>);
+
+    let mut words = Words(["b", "a", "d", "c"].map(String::from).into());
+    assert_eq!(words.range(String::from("b")..String::from("d")).count(), 2);
+    assert_eq!(words.first().map(String::as_str), Some("a"));
+    assert_eq!(words.last().map(String::as_str), Some("d"));
+    let upper = words.split_off("c");
+    assert_eq!(upper, Words(["c", "d"].map(String::from).into()));
+    assert_eq!(words.pop_first().as_deref(), Some("a"));
+    assert_eq!(words.pop_last().as_deref(), Some("b"));
+}
+
+#[test]
+fn hash_set_membership() {
+    let mut set = HashSetTuple::from_iter([1, 2]);
+    assert!(set.contains(&1));
+    assert!(!set.contains(&3));
+    assert!(set.insert(3));
+    assert!(!set.insert(3));
+    assert_eq!(set.get(&2), Some(&2));
+    assert_eq!(set.replace(2), Some(2));
+    assert_eq!(set.take(&2), Some(2));
+    assert!(set.remove(&1));
+    assert!(!set.remove(&1));
+    assert_eq!(set, HashSetTuple::from_iter([3]));
+}
+
+#[test]
+fn btree_set_membership() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(membership)]
+    struct Tags(std::collections::BTreeSet<String>);
+
+    let mut tags = Tags(Default::default());
+    assert!(tags.insert("a".to_string()));
+    assert!(tags.contains("a"));
+    assert_eq!(tags.get("a").map(String::as_str), Some("a"));
+    assert_eq!(tags.take("a").as_deref(), Some("a"));
+    assert!(!tags.remove("a"));
+    assert_eq!(tags.replace("b".to_string()), None);
+}
+
+#[test]
+fn hash_set_ops() {
+    let a = HashSetTuple::from_iter([1, 2, 3]);
+    let b = HashSetTuple::from_iter([2, 3, 4]);
+    assert_eq!(a.union(&b), HashSetTuple::from_iter([1, 2, 3, 4]));
+    assert_eq!(a.intersection(&b), HashSetTuple::from_iter([2, 3]));
+    assert_eq!(a.difference(&b), HashSetTuple::from_iter([1]));
+    assert_eq!(a.symmetric_difference(&b), HashSetTuple::from_iter([1, 4]));
+    assert!(HashSetTuple::from_iter([2]).is_subset(&a));
+    assert!(a.is_superset(&HashSetTuple::from_iter([1, 3])));
+    assert!(a.is_disjoint(&HashSetTuple::from_iter([5])));
+
+    assert_eq!(&a | &b, a.union(&b));
+    assert_eq!(&a & &b, a.intersection(&b));
+    assert_eq!(&a - &b, a.difference(&b));
+    assert_eq!(&a ^ &b, a.symmetric_difference(&b));
+    assert_eq!(
+        HashSetTuple::from_iter([1, 2, 3]) ^ HashSetTuple::from_iter([2, 3, 4]),
+        HashSetTuple::from_iter([1, 4])
+    );
+
+    let mut c = HashSetTuple::from_iter([1, 2]);
+    c |= &b;
+    assert_eq!(c, HashSetTuple::from_iter([1, 2, 3, 4]));
+    c -= HashSetTuple::from_iter([4]);
+    assert_eq!(c, HashSetTuple::from_iter([1, 2, 3]));
+    c &= &b;
+    assert_eq!(c, HashSetTuple::from_iter([2, 3]));
+    c ^= &b;
+    assert_eq!(c, HashSetTuple::from_iter([4]));
+}
+
+#[test]
+fn btree_set_ops() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(set_ops, from_iter)]
+    struct Permissions<T: Ord>(std::collections::BTreeSet<T>);
+
+    let read_write = Permissions::from_iter(["read", "write"]);
+    let read_exec = Permissions::from_iter(["read", "exec"]);
+    assert_eq!(
+        read_write.union(&read_exec),
+        Permissions::from_iter(["exec", "read", "write"])
+    );
+    assert_eq!(&read_write & &read_exec, Permissions::from_iter(["read"]));
+    assert_eq!(read_write - read_exec, Permissions::from_iter(["write"]));
+}
+
+#[test]
+fn hash_map_with_custom_hasher() {
+    use std::hash::BuildHasherDefault;
+
+    #[derive(Default)]
+    struct ZeroHasher;
+    impl std::hash::Hasher for ZeroHasher {
+        fn finish(&self) -> u64 {
+            0
+        }
+        fn write(&mut self, _bytes: &[u8]) {}
+    }
+
+    #[derive(Plural)]
+    struct Scores(HashMap<u8, bool, BuildHasherDefault<ZeroHasher>>);
+
+    let mut scores = Scores::new();
+    scores.reserve(2);
+    scores.extend([(1, true)]);
+    *scores.entry(2).or_default() = true;
+    assert_eq!(scores.len(), 2);
+    let _: &BuildHasherDefault<ZeroHasher> = scores.hasher();
+    assert!(Scores::with_capacity(4).capacity() >= 4);
+    assert!(Scores::with_hasher(Default::default()).is_empty());
+    assert!(Scores::with_capacity_and_hasher(4, Default::default()).capacity() >= 4);
+}
+
+#[test]
+fn hash_set_generic_hasher() {
+    use std::hash::{BuildHasher, RandomState};
+
+    #[derive(Plural)]
+    struct Tags<S: BuildHasher>(std::collections::HashSet<&'static str, S>);
+
+    let mut tags = Tags::with_hasher(RandomState::new());
+    assert!(tags.insert("a"));
+    assert!(tags.contains("a"));
+    let _: &RandomState = tags.hasher();
+
+    let tags: Tags<RandomState> = ["a", "b"].into_iter().collect();
+    assert_eq!(tags.len(), 2);
+    assert!(Tags::<RandomState>::new().is_empty());
+    assert_eq!((&tags | &Tags::from_iter(["c"])).len(), 3);
+}
+
+#[test]
+fn vec_with_allocator() {
+    use allocator_api2::{alloc::Global, vec::Vec};
+
+    #[derive(Plural)]
+    struct Numbers<A: allocator_api2::alloc::Allocator>(Vec<u8, A>);
+
+    let mut numbers = Numbers::new_in(Global);
+    numbers.extend([1, 2]);
+    assert_eq!(
+        numbers.iter().copied().collect::<std::vec::Vec<_>>(),
+        [1, 2]
+    );
+    let _: &Global = numbers.allocator();
+    assert!(Numbers::with_capacity_in(4, Global).capacity() >= 4);
+    assert!(Numbers::<Global>::with_capacity(4).capacity() >= 4);
+    assert!(Numbers::<Global>::new().is_empty());
+
+    let collected: Numbers<Global> = [3, 4].into_iter().collect();
+    assert_eq!(collected.len(), 2);
+}
+
+#[test]
+fn boxed_slice() {
+    #[derive(Plural, Debug, PartialEq)]
+    struct Numbers(Box<[u8]>);
+
+    let numbers: Numbers = [1, 2].into_iter().collect();
+    assert_eq!(numbers.len(), 2);
+    assert_eq!((&numbers).into_iter().copied().sum::<u8>(), 3);
+    assert_eq!(numbers.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn deref_inner() {
+    #[derive(Plural)]
+    #[plural(deref, deref_mut)]
+    struct Numbers(Vec<u8>);
+
+    let mut numbers = Numbers::new();
+    numbers.push(1);
+    let _: &Vec<u8> = &numbers;
+    assert_eq!(numbers.first(), Some(&1));
+}
+
+#[test]
+fn deref_slice() {
+    #[derive(Plural)]
+    #[plural(len, deref = slice)]
+    struct Numbers(Vec<u8>);
+
+    let numbers = Numbers(vec![3, 1, 2]);
+    let _: &[u8] = &numbers;
+    assert_eq!(numbers.len(), 3);
+    assert_eq!(numbers.last(), Some(&2));
+
+    #[derive(Plural)]
+    #[plural(deref_mut = slice)]
+    struct BoxedNumbers(Box<[u8]>);
+
+    let mut numbers = BoxedNumbers(Box::new([3, 1, 2]));
+    numbers.sort();
+    assert_eq!(&*numbers, &[1, 2, 3]);
+}
+
+#[test]
+fn as_ref_as_mut() {
+    #[derive(Plural)]
+    #[plural(as_ref, as_mut)]
+    struct Numbers(Vec<u8>);
+
+    fn sum(numbers: impl AsRef<[u8]>) -> u8 {
+        numbers.as_ref().iter().sum()
+    }
+    fn push(numbers: &mut impl AsMut<Vec<u8>>) {
+        numbers.as_mut().push(3);
+    }
+
+    let mut numbers = Numbers(vec![1, 2]);
+    push(&mut numbers);
+    AsMut::<[u8]>::as_mut(&mut numbers)[0] = 0;
+    assert_eq!(AsRef::<Vec<u8>>::as_ref(&numbers), &vec![0, 2, 3]);
+    assert_eq!(sum(numbers), 5);
+}
+
+#[test]
+fn borrow_slice() {
+    #[derive(Plural, PartialEq, Eq, Hash)]
+    #[plural(borrow)]
+    struct Path(Vec<&'static str>);
+
+    let mut sizes = HashMap::new();
+    sizes.insert(Path(vec!["usr", "bin"]), 10);
+    assert_eq!(sizes.get(["usr", "bin"].as_slice()), Some(&10));
+}
+
+#[test]
+fn default_without_item_bound() {
+    struct NotDefault;
+
+    #[derive(Plural)]
+    #[plural(len, default)]
+    struct Items<T>(Vec<T>);
+
+    assert_eq!(Items::<NotDefault>::default().len(), 0);
+
+    #[derive(Plural)]
+    #[plural(default)]
+    struct Index<K: Eq + std::hash::Hash, V>(HashMap<K, V>);
+
+    assert!(Index::<u8, NotDefault>::default().is_empty());
+}
+
+#[test]
+fn debug_transparent() {
+    #[derive(Plural)]
+    #[plural(debug = transparent)]
+    struct Numbers(Vec<u8>);
+
+    assert_eq!(format!("{:?}", Numbers(vec![1, 2, 3])), "[1, 2, 3]");
+    assert_eq!(format!("{:#?}", Numbers(vec![1])), "[\n    1,\n]");
+}
+
+#[test]
+fn debug_named() {
+    #[derive(Plural)]
+    #[plural(debug = named)]
+    struct Numbers<T>(Vec<T>);
+
+    assert_eq!(format!("{:?}", Numbers(vec![1, 2, 3])), "Numbers[1, 2, 3]");
+}
+
+#[test]
+fn debug_max_items() {
+    #[derive(Plural)]
+    #[plural(debug(max_items = 2))]
+    struct Numbers(Vec<u32>);
+
+    assert_eq!(format!("{:?}", Numbers(vec![1, 2])), "[1, 2]");
+    assert_eq!(
+        format!("{:?}", Numbers((1..=100_000).collect())),
+        "[1, 2, … (99998 more)]"
+    );
+    assert_eq!(
+        format!("{:#?}", Numbers(vec![1, 2, 3])),
+        "[\n    1,\n    2,\n    … (1 more),\n]"
+    );
+
+    #[derive(Plural)]
+    #[plural(debug(named, max_items = 1))]
+    struct Scores(std::collections::BTreeMap<&'static str, u8>);
+
+    assert_eq!(
+        format!("{:?}", Scores::from_iter([("a", 1), ("b", 2)])),
+        r#"Scores{"a": 1, … (1 more)}"#
+    );
+    assert_eq!(
+        format!("{:#?}", Scores::from_iter([("a", 1), ("b", 2)])),
+        "Scores{\n    \"a\": 1,\n    … (1 more),\n}"
+    );
+}
+
+#[test]
+fn debug_sorted() {
+    #[derive(Plural)]
+    #[plural(debug(sorted))]
+    struct Scores(HashMap<&'static str, u8>);
+
+    assert_eq!(
+        format!("{:?}", Scores::from_iter([("c", 3), ("a", 1), ("b", 2)])),
+        r#"{"a": 1, "b": 2, "c": 3}"#
+    );
+
+    #[derive(Plural)]
+    #[plural(debug(named, sorted, max_items = 2))]
+    struct Tags(std::collections::HashSet<u8>);
+
+    assert_eq!(
+        format!("{:?}", Tags::from_iter([5, 3, 1, 4])),
+        "Tags{1, 3, … (2 more)}"
+    );
+}
+
+#[test]
+fn display() {
+    #[derive(Plural)]
+    #[plural(display)]
+    struct Numbers(Vec<u8>);
+
+    assert_eq!(Numbers(vec![1, 2, 3]).to_string(), "1, 2, 3");
+    assert_eq!(Numbers(vec![]).to_string(), "");
+
+    #[derive(Plural)]
+    #[plural(display(sep = "; ", prefix = "[", suffix = "]"))]
+    struct Labels(std::collections::BTreeMap<&'static str, u8>);
+
+    assert_eq!(
+        Labels::from_iter([("b", 2), ("a", 1)]).to_string(),
+        "[a=1; b=2]"
+    );
+}
+
+#[test]
+fn from_str() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(from_str)]
+    struct Ids(Vec<u32>);
+
+    assert_eq!("1, 2,3".parse(), Ok(Ids(vec![1, 2, 3])));
+    assert_eq!("".parse(), Ok(Ids(vec![])));
+    let error = "1,x".parse::<Ids>().unwrap_err();
+    assert_eq!(error.index, 1);
+    assert_eq!(error.error, "x".parse::<u32>().unwrap_err());
+    assert_eq!(
+        error.to_string(),
+        "failed to parse item 1: invalid digit found in string"
+    );
+
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(from_str(sep = ";", kv_sep = ":"))]
+    struct Labels(std::collections::BTreeMap<String, u8>);
+
+    assert_eq!(
+        "a: 1; b:2".parse(),
+        Ok(Labels::from_iter([
+            ("a".to_string(), 1),
+            ("b".to_string(), 2)
+        ]))
+    );
+    let error = "a:1;b".parse::<Labels>().unwrap_err();
+    assert_eq!(error, ParseLabelsError::MissingSeparator { index: 1 });
+    assert!(matches!(
+        "a:x".parse::<Labels>(),
+        Err(ParseLabelsError::Value { index: 0, .. })
+    ));
+}
+
+#[test]
+fn partial_eq_inner() {
+    #[derive(Plural, Debug)]
+    #[plural(partial_eq)]
+    struct Numbers<T>(Vec<T>);
+
+    let numbers = Numbers(vec![1, 2]);
+    assert_eq!(numbers, vec![1, 2]);
+    assert_eq!(vec![1, 2], numbers);
+    assert_eq!(numbers, [1, 2]);
+    assert_eq!([1, 2], numbers);
+    assert_eq!(numbers, [1, 2][..]);
+    assert_eq!([1, 2][..], numbers);
+    assert_eq!(numbers, &[1, 2][..]);
+    assert_eq!(&[1, 2][..], numbers);
+    assert_ne!(numbers, [1]);
+
+    #[derive(Plural, Debug)]
+    #[plural(partial_eq)]
+    struct Queue(std::collections::VecDeque<u8>);
+
+    assert_eq!(Queue::from_iter([1, 2]), [1, 2]);
+
+    #[derive(Plural, Debug)]
+    #[plural(partial_eq)]
+    struct Flags(HashMap<u8, bool>);
+
+    assert_eq!(Flags::from_iter([(1, true)]), HashMap::from([(1, true)]));
+    assert_eq!(HashMap::from([(1, true)]), Flags::from_iter([(1, true)]));
+}
+
+#[test]
+fn extend_and_collect_refs() {
+    let other = VecTuple(vec![3, 4]);
+    let mut numbers = VecTuple(vec![1, 2]);
+    numbers.extend(other.iter());
+    assert_eq!(numbers, VecTuple(vec![1, 2, 3, 4]));
+    assert_eq!(other.iter().collect::<VecTuple>(), other);
+
+    let flags = HashMap::from([(1, true)]);
+    let mut map = HashMapTuple::from_iter([(2, false)]);
+    map.extend(&flags);
+    assert_eq!(map, HashMapTuple::from_iter([(1, true), (2, false)]));
+    assert_eq!(flags.iter().collect::<HashMapTuple>(), HashMapTuple(flags));
+}
+
+#[test]
+fn from_std() {
+    assert_eq!(VecTuple::from([1, 2]), VecTuple(vec![1, 2]));
+    assert_eq!(VecTuple::from(&[1, 2][..]), VecTuple(vec![1, 2]));
+    assert_eq!(
+        VecTuple::from(vec![1, 2].into_boxed_slice()),
+        VecTuple(vec![1, 2])
+    );
+    assert_eq!(
+        HashMapTuple::from([(1, true)]),
+        HashMapTuple(HashMap::from([(1, true)]))
+    );
+    assert_eq!(HashSetTuple::from([1, 2]), HashSetTuple::from_iter([1, 2]));
+
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(from_std)]
+    struct Words(std::collections::VecDeque<String>);
+
+    assert_eq!(
+        Words::from(["a".to_string()]),
+        Words(["a".to_string()].into())
+    );
+}
+
+#[test]
+fn into_slice() {
+    use std::{rc::Rc, sync::Arc};
+
+    #[derive(Plural)]
+    #[plural(into_slice)]
+    struct Numbers(Vec<u32>);
+
+    assert_eq!(&*Numbers(vec![1, 2]).into_boxed_slice(), &[1, 2]);
+    assert_eq!(&*Numbers(vec![1, 2]).into_arc_slice(), &[1, 2]);
+    assert_eq!(&*Numbers(vec![1, 2]).into_rc_slice(), &[1, 2]);
+    let boxed: Box<[u32]> = Numbers(vec![3]).into();
+    let arc: Arc<[u32]> = Numbers(vec![3]).into();
+    let rc: Rc<[u32]> = Numbers(vec![3]).into();
+    assert_eq!((&*boxed, &*arc, &*rc), (&[3][..], &[3][..], &[3][..]));
+}
+
+#[test]
+fn concat() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(concat)]
+    struct Numbers(Vec<u32>);
+
+    let mut numbers = Numbers(vec![1]) + Numbers(vec![2, 3]);
+    numbers += Numbers(vec![4]);
+    assert_eq!(numbers, Numbers(vec![1, 2, 3, 4]));
+    numbers.extend([Numbers(vec![5]), Numbers(vec![6])]);
+    assert_eq!(numbers.len(), 6);
+
+    let batches = vec![Numbers(vec![1]), Numbers(vec![]), Numbers(vec![2])];
+    assert_eq!(batches.into_iter().sum::<Numbers>(), Numbers(vec![1, 2]));
+    assert_eq!(std::iter::empty().sum::<Numbers>(), Numbers(vec![]));
+}
+
+#[test]
+fn hash_unordered() {
+    use std::collections::HashSet;
+    use std::hash::{BuildHasher, RandomState};
+
+    #[derive(Plural, Debug)]
+    #[plural(hash(unordered))]
+    struct Tags(HashSet<String>);
+
+    let a = Tags::from_iter((0..100).map(|n| n.to_string()));
+    let b = Tags::from_iter((0..100).rev().map(|n| n.to_string()));
+    assert_eq!(a, b);
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&a), state.hash_one(&b));
+    assert_ne!(state.hash_one(&a), state.hash_one(Tags::from_iter([])));
+
+    let mut groups = HashMap::new();
+    groups.insert(a, 1);
+    assert_eq!(groups.get(&b), Some(&1));
+
+    #[derive(Plural)]
+    #[plural(hash(unordered))]
+    struct Flags(HashMap<u8, bool>);
+
+    assert_eq!(
+        state.hash_one(Flags::from_iter([(1, true), (2, false)])),
+        state.hash_one(Flags::from_iter([(2, false), (1, true)]))
+    );
+}
+
+#[test]
+fn derive_bounded_on_inner() {
+    use std::marker::PhantomData;
+
+    struct Id<T>(u32, PhantomData<T>);
+
+    impl<T> Clone for Id<T> {
+        fn clone(&self) -> Self {
+            Id(self.0, PhantomData)
+        }
+    }
+
+    impl<T> PartialEq for Id<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl<T> Eq for Id<T> {}
+
+    impl<T> PartialOrd for Id<T> {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl<T> Ord for Id<T> {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    impl<T> std::hash::Hash for Id<T> {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
+
+    // Not `Clone`, `PartialEq`, ... on its own.
+    struct User;
+
+    #[derive(Plural)]
+    #[plural(derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord))]
+    struct Ids<T>(Vec<Id<T>>);
+
+    let ids = Ids::<User>::from_iter([Id(1, PhantomData), Id(2, PhantomData)]);
+    let cloned = ids.clone();
+    assert!(ids == cloned);
+    assert!(ids <= cloned);
+    assert_eq!(
+        ids.cmp(&Ids::from_iter([Id(3, PhantomData)])),
+        std::cmp::Ordering::Less
+    );
+    let state = std::hash::RandomState::new();
+    assert_eq!(
+        std::hash::BuildHasher::hash_one(&state, &ids),
+        std::hash::BuildHasher::hash_one(&state, &cloned)
+    );
+
+    #[derive(Plural)]
+    #[plural(derive(Clone, PartialEq))]
+    struct Forest(Vec<Forest>);
+
+    let forest = Forest::from_iter([Forest::new()]);
+    assert!(forest.clone() == forest);
+
+    #[derive(Plural, Debug)]
+    #[plural(derive(Clone, PartialEq, Eq, PartialOrd, Ord))]
+    struct Tree<T: Ord>(std::collections::BTreeMap<T, Tree<T>>);
+
+    let leaf = || Tree::<u8>::new();
+    let tree = Tree::from_iter([(1, Tree::from_iter([(2, leaf())])), (3, leaf())]);
+    assert_eq!(tree.clone(), tree);
+    assert!(tree > Tree::from_iter([(1, leaf())]));
+}
+
+#[test]
+fn non_empty() {
+    #[derive(Plural, Debug)]
+    #[plural(non_empty)]
+    struct Recipients(Vec<String>);
+
+    assert!(Recipients::try_from(Vec::new()).is_err());
+    assert_eq!(
+        Recipients::try_from_iter([]).unwrap_err().to_string(),
+        "Recipients must not be empty"
+    );
+    let mut recipients = Recipients::try_from_iter(["a".to_string()]).unwrap();
+    assert_eq!(recipients.first(), "a");
+    recipients.extend(["b".to_string()]);
+    assert_eq!(recipients.last(), "b");
+    assert_eq!(recipients.len(), 2);
+
+    #[derive(Plural)]
+    #[plural(len, from_inner, from_iter, non_empty)]
+    struct Queue(std::collections::VecDeque<u8>);
+
+    let queue = Queue::try_from(std::collections::VecDeque::from([3, 4])).unwrap();
+    assert_eq!((queue.first(), queue.last(), queue.len()), (&3, &4, 2));
+    assert_eq!(Queue::try_from_iter(0..0).err(), Some(EmptyQueueError));
+}
+
+#[test]
+fn min_max_len() {
+    #[derive(Plural, Debug)]
+    #[plural(min_len = 1, max_len = 3)]
+    struct Recipients(Vec<String>);
+
+    assert_eq!(
+        Recipients::try_from(Vec::new()).unwrap_err(),
+        RecipientsLenError::TooShort { len: 0 }
+    );
+    assert_eq!(
+        Recipients::try_from_iter(["a", "b", "c", "d"].map(String::from))
+            .unwrap_err()
+            .to_string(),
+        "Recipients must have at most 3 items, got 4"
+    );
+    let mut recipients = Recipients::try_from_iter(["a".to_string()]).unwrap();
+    assert_eq!(recipients.first(), "a");
+    recipients.try_push("b".to_string()).unwrap();
+    assert_eq!(
+        recipients.try_extend(["c", "d"].map(String::from)),
+        Err(RecipientsLenError::TooLong { len: 4 })
+    );
+    assert_eq!(recipients.len(), 2);
+    recipients.try_extend(["c".to_string()]).unwrap();
+    assert_eq!(
+        recipients.try_push("d".to_string()),
+        Err(RecipientsLenError::TooLong { len: 4 })
+    );
+    assert_eq!(recipients.last(), "c");
+
+    #[derive(Plural)]
+    #[plural(max_len = 2)]
+    struct Pair(HashMap<u8, u8>);
+
+    let mut pair = Pair::new();
+    assert!(pair.is_empty());
+    pair = Pair::try_from(HashMap::from([(1, 1), (2, 2)])).unwrap();
+    assert_eq!(pair.len(), 2);
+    assert!(Pair::try_from_iter((0..3).map(|n| (n, n))).is_err());
+
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(min_len = 0, deref_mut, as_mut, from_str)]
+    struct Unbounded(Vec<u8>);
+
+    let mut unbounded: Unbounded = "1, 2".parse().unwrap();
+    unbounded.push(3);
+    AsMut::<Vec<u8>>::as_mut(&mut unbounded).clear();
+    assert_eq!(unbounded, Unbounded::from(Vec::new()));
+}
+
+#[test]
+fn sorted() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(sorted)]
+    struct Scores(Vec<u32>);
+
+    let mut scores = Scores::from_iter([3, 1, 2]);
+    assert_eq!(scores, Scores(vec![1, 2, 3]));
+    scores.extend([0, 5]);
+    scores.extend(&[4]);
+    assert_eq!(scores, Scores::from(vec![5, 4, 3, 2, 1, 0]));
+    assert_eq!(scores.insert_sorted(2), 3);
+    assert_eq!(scores.position(&4), Some(5));
+    assert!(scores.contains(&0));
+    assert!(!scores.contains(&6));
+    assert_eq!(Scores::from([2, 1]), Scores(vec![1, 2]));
+
+    fn len(name: &&str) -> usize {
+        name.len()
+    }
+
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(sorted_by_key = len, max_len = 3)]
+    struct Names(std::collections::VecDeque<&'static str>);
+
+    let mut names = Names::try_from_iter(["ccc", "a"]).unwrap();
+    names.try_push("bb").unwrap();
+    assert_eq!(
+        names.iter().copied().collect::<Vec<_>>(),
+        ["a", "bb", "ccc"]
+    );
+    assert_eq!(names.position(&"bb"), Some(1));
+    assert_eq!(names.position(&"xy"), None);
+    assert!(names.try_push("d").is_err());
+
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(sorted_by_key = len)]
+    struct Words(Vec<&'static str>);
+
+    let words = Words::from_iter(["cc", "a", "aa", "bb"]);
+    assert_eq!(words, Words(vec!["a", "cc", "aa", "bb"]));
+    assert_eq!(words.position(&"bb"), Some(3));
+    assert!(words.contains(&"aa"));
+    assert!(!words.contains(&"zz"));
+
+    // Items without `PartialEq` can still be sorted by key.
+    struct Task {
+        priority: u8,
+    }
+
+    #[derive(Plural)]
+    #[plural(sorted_by_key = |task: &Task| task.priority)]
+    struct Tasks(Vec<Task>);
+
+    let mut tasks = Tasks::from_iter([Task { priority: 2 }, Task { priority: 1 }]);
+    assert_eq!(tasks.insert_sorted(Task { priority: 1 }), 1);
+    assert_eq!(
+        tasks.iter().map(|task| task.priority).collect::<Vec<_>>(),
+        [1, 1, 2]
+    );
+}
+
+#[test]
+fn unique() {
+    #[derive(Plural, Debug, PartialEq)]
+    #[plural(unique)]
+    struct Tags(Vec<&'static str>);
+
+    let mut tags = Tags::from_iter(["b", "a", "b"]);
+    assert_eq!(tags, Tags(vec!["b", "a"]));
+    assert!(tags.push("c"));
+    assert!(!tags.push("a"));
+    tags.extend(["d", "c", "d"]);
+    tags.extend(&["e", "b"]);
+    assert_eq!(tags, Tags(vec!["b", "a", "c", "d", "e"]));
+    assert_eq!(Tags::try_from(vec!["x", "y"]), Ok(Tags(vec!["x", "y"])));
+    let error = Tags::try_from(vec!["x", "y", "z", "y"]).unwrap_err();
+    assert_eq!(error, DuplicateTagsError { index: 3 });
+    assert_eq!(error.to_string(), "duplicate item at index 3");
+}
+
+#[test]
+fn entry_of_other_map_crates() {
+    // `entry` is left out, as this `Entry` type takes the hasher as well.
+    #[derive(Plural)]
+    struct Qualified(hashbrown::HashMap<u8, u8>);
+
+    let mut qualified = Qualified::new();
+    qualified.extend([(1, 2)]);
+    assert_eq!(qualified.iter().collect::<Vec<_>>(), [(&1, &2)]);
+}
+
+#[test]
+fn set_of_non_clone_items() {
+    use std::collections::{BTreeSet, HashSet};
+
+    #[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+    struct Id(u32);
+
+    #[derive(Plural)]
+    struct Ids(HashSet<Id>);
+
+    #[derive(Plural)]
+    struct SortedIds(BTreeSet<Id>);
+
+    let mut ids = Ids::from_iter([Id(1), Id(2)]);
+    ids |= Ids::from_iter([Id(3)]);
+    ids -= Ids::from_iter([Id(1)]);
+    assert!(ids.is_superset(&Ids::from_iter([Id(2), Id(3)])));
+    let sorted = SortedIds::from_iter([Id(2), Id(1)]) & SortedIds::from_iter([Id(2)]);
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), [&Id(2)]);
+}
+
+#[test]
+fn non_default_hasher() {
+    use std::hash::{BuildHasher, DefaultHasher, Hasher};
+
+    #[derive(Clone)]
+    struct Seeded(u64);
+
+    impl BuildHasher for Seeded {
+        type Hasher = DefaultHasher;
+        fn build_hasher(&self) -> DefaultHasher {
+            let mut hasher = DefaultHasher::new();
+            hasher.write_u64(self.0);
+            hasher
+        }
+    }
+
+    #[derive(Plural)]
+    struct Scores(std::collections::HashMap<u8, u8, Seeded>);
+
+    let mut scores = Scores::with_hasher(Seeded(7));
+    scores.extend([(1, 1)]);
+    *scores.entry(2).or_default() += 2;
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores.hasher().0, 7);
+    assert!(Scores::with_capacity_and_hasher(4, Seeded(1)).capacity() >= 4);
+}
+
+#[test]
+fn non_default_allocator() {
+    use allocator_api2::alloc::{AllocError, Allocator, Global};
+    use std::{alloc::Layout, ptr::NonNull};
+
+    struct Tagged(u8);
+
+    unsafe impl Allocator for Tagged {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    #[derive(Plural)]
+    struct Bytes(allocator_api2::vec::Vec<u8, Tagged>);
+
+    let mut bytes = Bytes::new_in(Tagged(3));
+    bytes.extend([1, 2]);
+    assert_eq!(bytes.len(), 2);
+    assert_eq!(bytes.allocator().0, 3);
+    assert!(Bytes::with_capacity_in(4, Tagged(1)).capacity() >= 4);
+}
+
+#[test]
+fn no_entry_without_empty_collections() {
+    // Resolves to the inherent `entry` instead if there is one, which would fail to compile.
+    trait NoEntry {
+        fn entry(&mut self, _key: u8) -> &'static str {
+            "absent"
+        }
+    }
+
+    #[derive(Plural)]
+    #[plural(non_empty)]
+    struct NonEmpty(std::collections::HashMap<u8, u8>);
+
+    impl NoEntry for NonEmpty {}
+
+    #[derive(Plural)]
+    #[plural(min_len = 2)]
+    struct AtLeastTwo(std::collections::BTreeMap<u8, u8>);
+
+    impl NoEntry for AtLeastTwo {}
+
+    let mut non_empty = NonEmpty::try_from(HashMap::from([(1, 1)])).unwrap();
+    assert_eq!(non_empty.entry(1), "absent");
+    let mut at_least_two = AtLeastTwo::try_from_iter([(1, 1), (2, 2)]).unwrap();
+    assert_eq!(at_least_two.entry(1), "absent");
+}
@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+#![no_std]
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use thisisplural::Plural;
+
+// Unqualified paths in a `no_std` crate mustn't make the generated code name `std`.
+#[derive(Plural, Debug, PartialEq)]
+#[plural(into_slice)]
+struct Numbers(Vec<u8>);
+
+#[derive(Plural)]
+#[plural(debug(sorted))]
+struct Counts(BTreeMap<u8, u8>);
+
+#[derive(Plural)]
+#[plural(max_len = 3)]
+struct Few(Vec<u8>);
+
+#[test]
+fn unqualified_alloc_collections() {
+    let boxed: Box<[u8]> = vec![1, 2].into_boxed_slice();
+    let numbers = Numbers::from(boxed);
+    assert_eq!(numbers, Numbers::from([1, 2]));
+    assert_eq!(&*numbers.into_boxed_slice(), &[1, 2]);
+
+    let mut counts = Counts::new();
+    *counts.entry(1).or_default() += 1;
+    assert_eq!(alloc::format!("{counts:?}"), "{1: 1}");
+
+    let mut few = Few::try_from_iter([1]).unwrap();
+    few.try_extend([2, 3]).unwrap();
+    assert!(few.try_extend([4]).is_err());
+    assert_eq!(few.len(), 3);
+}